@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate criterion;
+extern crate codepage_437;
+
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, BorrowFromCodepage, FromCodepage, ToCodepage};
+use criterion::{BenchmarkId, Criterion, Throughput};
+use std::borrow::Cow;
+
+
+/// Mostly-ASCII text, with the occasional non-overlapping character towards the end.
+fn mostly_ascii() -> String {
+    "Local news reports that the million Air Melanesiae aircraft has crashed this morning around 9:00am.\r\n".repeat(40) + "₧½ million."
+}
+
+/// A screen's worth of box drawing and shading, with some ASCII inside.
+fn mostly_box_drawing() -> String {
+    "╔══════════════════════════════════════╗\r\n║░░▒▒▓▓██ Main Menu ██▓▓▒▒░░║\r\n╟──────────────────────────────────────╢\r\n".repeat(40)
+}
+
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for &(name, ref unicode) in &[("mostly_ascii", mostly_ascii()), ("mostly_box_drawing", mostly_box_drawing())] {
+        for &(dialect_name, dialect) in &[("control", &CP437_CONTROL), ("wingdings", &CP437_WINGDINGS)] {
+            // Newlines are glyphs in wingdings
+            let cp437 = unicode.to_codepage(&CP437_CONTROL).unwrap().into_owned();
+            group.throughput(Throughput::Bytes(cp437.len() as u64));
+
+            group.bench_with_input(BenchmarkId::new(format!("borrow_from_codepage/{}", dialect_name), name),
+                                   &cp437,
+                                   |b, cp437| b.iter(|| Cow::borrow_from_codepage(&cp437[..], dialect)));
+            group.bench_with_input(BenchmarkId::new(format!("from_codepage/{}", dialect_name), name),
+                                   &cp437,
+                                   |b, cp437| b.iter(|| String::from_codepage(cp437.clone(), dialect)));
+        }
+    }
+    group.finish();
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for &(name, ref unicode) in &[("mostly_ascii", mostly_ascii()), ("mostly_box_drawing", mostly_box_drawing())] {
+        for &(dialect_name, dialect) in &[("control", &CP437_CONTROL), ("wingdings", &CP437_WINGDINGS)] {
+            let unicode = String::from_codepage(unicode.to_codepage(&CP437_CONTROL).unwrap().into_owned(), dialect);
+            group.throughput(Throughput::Bytes(unicode.len() as u64));
+
+            group.bench_with_input(BenchmarkId::new(format!("to_codepage/{}", dialect_name), name),
+                                   &unicode,
+                                   |b, unicode| b.iter(|| unicode.to_codepage(dialect)));
+        }
+    }
+    group.finish();
+}
+
+
+criterion_group!(benches, decode, encode);
+criterion_main!(benches);
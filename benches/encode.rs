@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate criterion;
+extern crate codepage_437;
+
+use codepage_437::{CP437_CONTROL, SingleByteCodepage, ToCodepage};
+use criterion::{BenchmarkId, Criterion, Throughput};
+use std::char;
+
+
+/// Box drawing, which goes through the encode table rather than the all-overlapping fast path.
+const UNICODE: &str = "╔══════════╗ ║ ░▒▓█ ½¼ ║ ╚══════════╝ ";
+
+
+fn remapped(remaps: u32) -> SingleByteCodepage {
+    let mut dialect = CP437_CONTROL.clone();
+    for i in 0..remaps {
+        dialect.remap(0x80 + (i % 0x80) as u8, char::from_u32(0xF700 + i).unwrap());
+    }
+    dialect
+}
+
+fn encode(c: &mut Criterion) {
+    let unicode = UNICODE.repeat(64);
+
+    let mut group = c.benchmark_group("encode");
+    group.throughput(Throughput::Bytes(unicode.len() as u64));
+    for &remaps in &[0, 1, 16, 64, 256, 1024] {
+        let dialect = remapped(remaps);
+        group.bench_with_input(BenchmarkId::new("remaps", remaps), &unicode, |b, unicode| b.iter(|| unicode.to_codepage(&dialect)));
+    }
+    group.finish();
+}
+
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
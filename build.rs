@@ -15,7 +15,8 @@ struct Mapping {
 }
 
 impl Mapping {
-    pub fn from_record(record: csv::StringRecord) -> Result<Mapping, String> {
+    /// Parse a record, which maps to nothing if its Unicode column is empty.
+    pub fn from_record(record: csv::StringRecord) -> Result<Result<Mapping, u8>, String> {
         if record.len() != 3 {
             return Err(format!("Invalid record length ({}, should be 3)", record.len()));
         }
@@ -26,7 +27,7 @@ impl Mapping {
             return Err(format!("Invalid cp437 code prefix (\"{}\", should be \"0x\")", &cp437[..2]));
         }
         let cp437 = &cp437[2..];
-        if !cp437.chars().all(|c| (c >= '0' && c <= '9') || (c >= 'A' && c <= 'F') || (c >= 'a' && c <= 'f')) {
+        if !cp437.chars().all(|c| c.is_ascii_hexdigit()) {
             return Err(format!("cp437 code \"0x{}\" not hex", cp437));
         }
         if cp437.chars().count() > 2 {
@@ -34,48 +35,161 @@ impl Mapping {
         }
         let cp437 = u8::from_str_radix(cp437, 16).unwrap();
 
-        if &unicode[..2] != "0x" {
-            return Err(format!("Invalid Unicode code prefix (\"{}\", should be \"0x\")", &unicode[..2]));
+        if unicode.is_empty() {
+            return Ok(Err(cp437));
         }
-        let unicode = &unicode[2..];
-        if !unicode.chars().all(|c| (c >= '0' && c <= '9') || (c >= 'A' && c <= 'F') || (c >= 'a' && c <= 'f')) {
-            return Err(format!("Unicode code \"0x{}\" not hex", unicode));
+        let unicode = parse_unicode(unicode)?;
+
+        Ok(Ok(Mapping {
+            cp437,
+            unicode,
+            comment: comment.to_string(),
+        }))
+    }
+
+    /// Get the mappings, and the codes mapped to nothing.
+    pub fn from_mappings<P: AsRef<Path>>(p: P) -> (Vec<Mapping>, Vec<u8>) {
+        let mut ret = (Vec::new(), Vec::new());
+        for record in csv::ReaderBuilder::new().delimiter(b'\t').from_path(p).unwrap().into_records().map(Result::unwrap) {
+            match Mapping::from_record(record).unwrap() {
+                Ok(mapping) => ret.0.push(mapping),
+                Err(undefined) => ret.1.push(undefined),
+            }
         }
-        if unicode.chars().count() > 8 {
-            return Err(format!("Unicode code \"0x{}\" too big", unicode));
+        ret
+    }
+}
+
+
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+struct DbcsMapping {
+    code: u16,
+    unicode: char,
+}
+
+impl DbcsMapping {
+    /// Parse a record, whose code is a single byte, or a lead byte followed by a trail byte.
+    pub fn from_record(record: csv::StringRecord) -> Result<DbcsMapping, String> {
+        if record.len() != 3 {
+            return Err(format!("Invalid record length ({}, should be 3)", record.len()));
         }
-        let unicode = u32::from_str_radix(unicode, 16).unwrap();
-        let unicode = if let Some(unicode) = char::from_u32(unicode) {
-            unicode
-        } else {
-            return Err(format!("Unicode code 0x{:X} out of range", unicode));
-        };
 
-        Ok(Mapping {
-            cp437: cp437,
-            unicode: unicode,
+        let (code, unicode) = (record.get(0).unwrap(), record.get(1).unwrap());
+
+        if &code[..2] != "0x" {
+            return Err(format!("Invalid code prefix (\"{}\", should be \"0x\")", &code[..2]));
+        }
+        let code = &code[2..];
+        if !code.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Code \"0x{}\" not hex", code));
+        }
+        if code.chars().count() > 4 {
+            return Err(format!("Code \"0x{}\" too big", code));
+        }
+        let code = u16::from_str_radix(code, 16).unwrap();
+
+        Ok(DbcsMapping {
+            code,
+            unicode: parse_unicode(unicode)?,
+        })
+    }
+
+    pub fn from_mappings<P: AsRef<Path>>(p: P) -> Vec<DbcsMapping> {
+        let mut ret = Vec::new();
+        for record in csv::ReaderBuilder::new().delimiter(b'\t').from_path(p).unwrap().into_records().map(Result::unwrap) {
+            ret.push(DbcsMapping::from_record(record).unwrap());
+        }
+        ret
+    }
+}
+
+
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+struct LeadBytes {
+    first: u8,
+    last: u8,
+}
+
+impl LeadBytes {
+    pub fn from_lead_bytes<P: AsRef<Path>>(p: P) -> Vec<LeadBytes> {
+        let mut ret = Vec::new();
+        for record in csv::ReaderBuilder::new().delimiter(b'\t').from_path(p).unwrap().into_records().map(Result::unwrap) {
+            let mut range = record.iter().map(|b| if &b[..2] == "0x" { u8::from_str_radix(&b[2..], 16).ok() } else { None });
+            match (range.next(), range.next(), range.next()) {
+                (Some(Some(first)), Some(Some(last)), None) if first <= last => ret.push(LeadBytes { first, last }),
+                _ => panic!("Invalid lead byte range {:?}", record),
+            }
+        }
+        ret
+    }
+}
+
+
+#[derive(Debug, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+struct Transliteration {
+    unicode: char,
+    transliteration: String,
+    comment: String,
+}
+
+impl Transliteration {
+    pub fn from_record(record: csv::StringRecord) -> Result<Transliteration, String> {
+        if record.len() != 3 {
+            return Err(format!("Invalid record length ({}, should be 3)", record.len()));
+        }
+
+        let (unicode, transliteration, comment) = (record.get(0).unwrap(), record.get(1).unwrap(), record.get(2).unwrap());
+
+        let unicode = parse_unicode(unicode)?;
+        let transliteration = transliteration.split(' ').map(parse_unicode).collect::<Result<String, String>>()?;
+
+        Ok(Transliteration {
+            unicode,
+            transliteration,
             comment: comment.to_string(),
         })
     }
 
-    pub fn from_mappings<P: AsRef<Path>>(p: P) -> Vec<Mapping> {
+    pub fn from_transliterations<P: AsRef<Path>>(p: P) -> Vec<Transliteration> {
         let mut ret = Vec::new();
-        for record in csv::ReaderBuilder::new().delimiter('\t' as u8).from_path(p).unwrap().into_records().map(Result::unwrap) {
-            ret.push(Mapping::from_record(record).unwrap());
+        for record in csv::ReaderBuilder::new().delimiter(b'\t').from_path(p).unwrap().into_records().map(Result::unwrap) {
+            ret.push(Transliteration::from_record(record).unwrap());
         }
         ret
     }
 }
 
 
+fn parse_unicode(unicode: &str) -> Result<char, String> {
+    if &unicode[..2] != "0x" {
+        return Err(format!("Invalid Unicode code prefix (\"{}\", should be \"0x\")", &unicode[..2]));
+    }
+    let unicode = &unicode[2..];
+    if !unicode.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Unicode code \"0x{}\" not hex", unicode));
+    }
+    if unicode.chars().count() > 8 {
+        return Err(format!("Unicode code \"0x{}\" too big", unicode));
+    }
+    let unicode = u32::from_str_radix(unicode, 16).unwrap();
+    if let Some(unicode) = char::from_u32(unicode) {
+        Ok(unicode)
+    } else {
+        Err(format!("Unicode code 0x{:X} out of range", unicode))
+    }
+}
+
+
 fn main() {
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR env var nonexistant/non-Unicode");
     let mut specs_rs = File::create(PathBuf::from(format!("{}/dialects.rs", out_dir))).unwrap();
 
+    println!("cargo:rerun-if-changed=dialect-specs");
     for dir in fs::read_dir("dialect-specs").unwrap().map(Result::unwrap).filter(|f| f.file_type().unwrap().is_dir()) {
         let dialect_name_func = dir.file_name().to_str().unwrap().to_lowercase();
         let dialect_name_type = dir.file_name().to_str().unwrap().to_uppercase();
         let dialect_name_init = dialect_name_type.clone() + "_INIT";
+        let dialect_name_overlap_mask = dialect_name_type.clone() + "_OVERLAP_MASK";
 
         let cp437_overlap_func = format!("{}_cp437_overlaps", dialect_name_func);
         let unicode_overlap_func = format!("{}_unicode_overlaps", dialect_name_func);
@@ -86,84 +200,313 @@ fn main() {
         let documentation_md = dir.path().join("documentation.md");
         let overlaps_rs = dir.path().join("overlaps.rs");
 
+        // Dialects sharing nothing with ASCII, like the EBCDIC ones, have no overlaps.rs, and nothing overlaps therein
+        let overlaps = overlaps_rs.exists();
+
         println!("cargo:rerun-if-changed={}", values_tsv.display());
         println!("cargo:rerun-if-changed={}", variants_tsv.display());
         println!("cargo:rerun-if-changed={}", documentation_md.display());
-        println!("cargo:rerun-if-changed={}", overlaps_rs.display());
+        if overlaps {
+            println!("cargo:rerun-if-changed={}", overlaps_rs.display());
+        }
 
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs).unwrap();
         writeln!(specs_rs, "// {} start", dir.path().display()).unwrap();
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "").unwrap();
-
-        for line in BufReader::new(File::open(&overlaps_rs).unwrap()).lines().map(Result::unwrap) {
-            if line.contains("DIALECT_OVERLAP_CP437") || line.contains("DIALECT_OVERLAP_UNICODE") {
-                specs_rs.write_all(line.replace("DIALECT_OVERLAP_CP437", &cp437_overlap_func)
-                        .replace("DIALECT_OVERLAP_UNICODE", &unicode_overlap_func)
-                        .as_bytes())
-                    .unwrap();
-            } else {
-                specs_rs.write_all(line.as_bytes()).unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs).unwrap();
+
+        if overlaps {
+            for line in BufReader::new(File::open(&overlaps_rs).unwrap()).lines().map(Result::unwrap) {
+                if line.contains("DIALECT_OVERLAP_CP437") || line.contains("DIALECT_OVERLAP_UNICODE") {
+                    specs_rs.write_all(line.replace("DIALECT_OVERLAP_CP437", &cp437_overlap_func)
+                            .replace("DIALECT_OVERLAP_UNICODE", &unicode_overlap_func)
+                            .as_bytes())
+                        .unwrap();
+                } else {
+                    specs_rs.write_all(line.as_bytes()).unwrap();
+                }
+                writeln!(specs_rs).unwrap();
             }
-            writeln!(specs_rs).unwrap();
         }
 
-        let primary_mappings = Mapping::from_mappings(&values_tsv);
-        let variant_mappings = Mapping::from_mappings(&variants_tsv);
+        let (primary_mappings, undefined) = Mapping::from_mappings(&values_tsv);
+        let (variant_mappings, undefined_variants) = Mapping::from_mappings(&variants_tsv);
+        if !undefined_variants.is_empty() {
+            panic!("{}: variants mapped to nothing", variants_tsv.display());
+        }
+        let mut undefined_mask = [0u128; 2];
+        for &cp437 in &undefined {
+            undefined_mask[cp437 as usize / 128] |= 1 << (cp437 % 128);
+        }
+
+        writeln!(specs_rs).unwrap();
+        if overlaps {
+            writeln!(specs_rs, "const {}: u128 = {{", dialect_name_overlap_mask).unwrap();
+            writeln!(specs_rs, "\tlet mut mask = 0;").unwrap();
+            writeln!(specs_rs, "\tlet mut b = 0;").unwrap();
+            writeln!(specs_rs, "\twhile b < 0x80 {{").unwrap();
+            writeln!(specs_rs, "\t\tif {}(b) && {}(b as char) {{", cp437_overlap_func, unicode_overlap_func).unwrap();
+            writeln!(specs_rs, "\t\t\tmask |= 1 << b;").unwrap();
+            writeln!(specs_rs, "\t\t}}").unwrap();
+            writeln!(specs_rs, "\t\tb += 1;").unwrap();
+            writeln!(specs_rs, "\t}}").unwrap();
+            if undefined_mask[0] != 0 {
+                writeln!(specs_rs, "\tmask & !0x{:X}", undefined_mask[0]).unwrap();
+            } else {
+                writeln!(specs_rs, "\tmask").unwrap();
+            }
+            writeln!(specs_rs, "}};").unwrap();
+        } else {
+            writeln!(specs_rs, "const {}: u128 = 0;", dialect_name_overlap_mask).unwrap();
+        }
 
         let mut decode_array = vec![('\x00', String::new()); 256];
-        for i in 0..256 {
-            decode_array[i] = (i as u8 as char, String::new());
+        for (i, entry) in decode_array.iter_mut().enumerate() {
+            *entry = (i as u8 as char, String::new());
         }
         for &Mapping { cp437, unicode, ref comment } in &primary_mappings {
             decode_array[cp437 as usize] = (unicode, comment.clone());
         }
+        for &cp437 in &undefined {
+            decode_array[cp437 as usize].1 = "UNDEFINED".to_string();
+        }
 
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
         writeln!(specs_rs, "fn {}(unicode: char) -> Option<u8> {{", encode_func).unwrap();
         writeln!(specs_rs, "\tSome(match unicode {{").unwrap();
-        for &mapp in &[&primary_mappings, &variant_mappings] {
-            for &Mapping { cp437, unicode, ref comment } in mapp {
+        // A Unicode codepoint mapped more than once encodes to the last primary mapping,
+        // since the glyphs in the control area come before the characters proper;
+        // variants never override primary mappings.
+        let primary_encodes = primary_mappings.iter()
+            .enumerate()
+            .filter(|&(i, m)| primary_mappings[i + 1..].iter().all(|o| o.unicode != m.unicode));
+        let variant_encodes = variant_mappings.iter()
+            .enumerate()
+            .filter(|&(i, m)| primary_mappings.iter().chain(&variant_mappings[..i]).all(|o| o.unicode != m.unicode));
+        let encodes = [primary_encodes.map(|(_, m)| m).collect::<Vec<_>>(), variant_encodes.map(|(_, m)| m).collect()];
+        for mapp in &encodes {
+            for &&Mapping { cp437, unicode, ref comment } in mapp {
                 writeln!(specs_rs, "\t\t\'\\u{{{:06X}}}\' => 0x{:X},  // {}", unicode as u32, cp437, comment).unwrap();
             }
-            writeln!(specs_rs, "").unwrap();
+            writeln!(specs_rs).unwrap();
+        }
+        if overlaps {
+            // Undefined ASCII codepoints mustn't be encoded by the overlap fallback below
+            for &cp437 in undefined.iter().filter(|&&b| b < 0x80 && encodes.iter().flatten().all(|m| m.unicode != b as char)) {
+                writeln!(specs_rs, "\t\t'\\u{{{:06X}}}' => return None,  // UNDEFINED", cp437).unwrap();
+            }
+            writeln!(specs_rs, "\t\tc => if {}(c) {{ c as u8 }} else {{ return None }},", unicode_overlap_func).unwrap();
+        } else {
+            writeln!(specs_rs, "\t\t_ => return None,").unwrap();
         }
-        writeln!(specs_rs, "\t\tc => if {}(c) {{ c as u8 }} else {{ return None }},", unicode_overlap_func).unwrap();
         writeln!(specs_rs, "\t}})").unwrap();
         writeln!(specs_rs, "}}").unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
 
-        writeln!(specs_rs, "const {}: Cp437Dialect = Cp437Dialect {{", dialect_name_init).unwrap();
+        writeln!(specs_rs, "const {}: SingleByteCodepage = SingleByteCodepage {{", dialect_name_init).unwrap();
         writeln!(specs_rs, "\tcp437_to_unicode: [").unwrap();
         for &(unicode, ref comment) in decode_array.iter() {
             write!(specs_rs, "\t\t\'\\u{{{:06X}}}\',", unicode as u32).unwrap();
             if !comment.is_empty() {
                 writeln!(specs_rs, "  // {}", comment).unwrap();
             } else {
-                writeln!(specs_rs, "").unwrap();
+                writeln!(specs_rs).unwrap();
             }
         }
         writeln!(specs_rs, "\t],").unwrap();
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "\toverlap_unicode: {},", unicode_overlap_func).unwrap();
-        writeln!(specs_rs, "\toverlap_cp437: {},", cp437_overlap_func).unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs, "\toverlap_mask: {},", dialect_name_overlap_mask).unwrap();
+        writeln!(specs_rs, "\tundefined_mask: [0x{:X}, 0x{:X}],", undefined_mask[0], undefined_mask[1]).unwrap();
+        writeln!(specs_rs).unwrap();
         writeln!(specs_rs, "\tencode: {},", encode_func).unwrap();
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "\tremaps: Cow::Borrowed(&[]),").unwrap();
+        writeln!(specs_rs, "\tunicode_to_cp437: static_table!(&[]),").unwrap();
+        writeln!(specs_rs, "\tencode_index: static_table!(&[]),").unwrap();
+        writeln!(specs_rs).unwrap();
+        // Encoded Unicode codepoints not decoded from the codepoint they encode to
+        writeln!(specs_rs, "\taliases: static_table!(&[").unwrap();
+        for &&Mapping { cp437, unicode, ref comment } in encodes.iter().flatten().filter(|m| decode_array[m.cp437 as usize].0 != m.unicode) {
+            writeln!(specs_rs, "\t\t(\'\\u{{{:06X}}}\', 0x{:X}),  // {}", unicode as u32, cp437, comment).unwrap();
+        }
+        writeln!(specs_rs, "\t]),").unwrap();
+        writeln!(specs_rs).unwrap();
+        // Sorted by Unicode codepoint, the first comment (primary before variant) naming each one
+        let mut names: Vec<_> = primary_mappings.iter().chain(&variant_mappings).map(|m| (m.unicode, &m.comment[..])).collect();
+        names.sort_by_key(|&(unicode, _)| unicode);
+        names.dedup_by_key(|&mut (unicode, _)| unicode);
+        writeln!(specs_rs, "\tnames: &[").unwrap();
+        for (unicode, comment) in names {
+            writeln!(specs_rs, "\t\t(\'\\u{{{:06X}}}\', {:?}),", unicode as u32, comment).unwrap();
+        }
+        writeln!(specs_rs, "\t],").unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs, "\tremaps: static_table!(&[]),").unwrap();
         writeln!(specs_rs, "}};").unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
 
         for line in BufReader::new(File::open(&documentation_md).unwrap()).lines().map(Result::unwrap) {
             writeln!(specs_rs, "/// {}", line).unwrap();
         }
-        writeln!(specs_rs, "pub static {}: Cp437Dialect = {};", dialect_name_type, dialect_name_init).unwrap();
+        writeln!(specs_rs, "pub static {}: SingleByteCodepage = {};", dialect_name_type, dialect_name_init).unwrap();
 
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs).unwrap();
         writeln!(specs_rs, "// {} end", dir.path().display()).unwrap();
-        writeln!(specs_rs, "").unwrap();
-        writeln!(specs_rs, "").unwrap();
+        writeln!(specs_rs).unwrap();
+        writeln!(specs_rs).unwrap();
+    }
+
+    let mut dbcs_rs = File::create(PathBuf::from(format!("{}/dbcs.rs", out_dir))).unwrap();
+
+    println!("cargo:rerun-if-changed=dbcs-specs");
+    for dir in fs::read_dir("dbcs-specs").unwrap().map(Result::unwrap).filter(|f| f.file_type().unwrap().is_dir()) {
+        let dialect_name_type = dir.file_name().to_str().unwrap().to_uppercase();
+        let dialect_name_double = dialect_name_type.clone() + "_DOUBLE";
+        let dialect_name_encode = dialect_name_type.clone() + "_ENCODE";
+
+        let values_tsv = dir.path().join("values.tsv");
+        let duplicates_tsv = dir.path().join("duplicates.tsv");
+        let lead_bytes_tsv = dir.path().join("lead_bytes.tsv");
+        let documentation_md = dir.path().join("documentation.md");
+
+        println!("cargo:rerun-if-changed={}", values_tsv.display());
+        println!("cargo:rerun-if-changed={}", duplicates_tsv.display());
+        println!("cargo:rerun-if-changed={}", lead_bytes_tsv.display());
+        println!("cargo:rerun-if-changed={}", documentation_md.display());
+
+        let lead_bytes = LeadBytes::from_lead_bytes(&lead_bytes_tsv);
+        let is_lead = |b: u8| lead_bytes.iter().any(|r| r.first <= b && b <= r.last);
+        let values = DbcsMapping::from_mappings(&values_tsv);
+        let duplicates = DbcsMapping::from_mappings(&duplicates_tsv);
+
+        let mut single = [None; 256];
+        let mut double = vec![[0u16; 256]; 256];
+        for &DbcsMapping { code, unicode } in values.iter().chain(&duplicates) {
+            if unicode as u32 > 0xFFFF || unicode == '\0' && code > 0xFF {
+                panic!("{}: 0x{:04X} mapped to U+{:04X}, outside of the Basic Multilingual Plane, or to NUL", dir.path().display(), code, unicode as u32);
+            }
+            let lead = (code >> 8) as u8;
+            if code <= 0xFF && is_lead(code as u8) || code > 0xFF && !is_lead(lead) {
+                panic!("{}: 0x{:04X} mapped, but lead bytes are {:?}", dir.path().display(), code, lead_bytes);
+            }
+            if code <= 0xFF {
+                single[code as usize] = Some(unicode);
+            } else {
+                double[lead as usize][code as usize & 0xFF] = unicode as u16;
+            }
+        }
+
+        let mut encode: Vec<_> = values.iter().map(|m| (m.unicode as u16, m.code)).collect();
+        encode.sort();
+        if let Some(w) = encode.windows(2).find(|w| w[0].0 == w[1].0) {
+            panic!("{}: U+{:04X} mapped more than once; keep one, and move the others to duplicates.tsv", values_tsv.display(), w[0].0);
+        }
+        for m in &duplicates {
+            if encode.binary_search_by_key(&(m.unicode as u16), |&(u, _)| u).is_err() {
+                panic!("{}: U+{:04X} not in values.tsv", duplicates_tsv.display(), m.unicode as u32);
+            }
+        }
+
+        let leads: Vec<u8> = (0..=0xFFu8).filter(|&b| is_lead(b)).collect();
+        let mut undefined_mask = [0u128; 2];
+        for b in (0..=0xFFu8).filter(|&b| single[b as usize].is_none()) {
+            undefined_mask[b as usize / 128] |= 1 << (b % 128);
+        }
+        let overlap_mask = (0..0x80u8)
+            .filter(|&b| single[b as usize] == Some(b as char) && encode.binary_search(&(b as u16, b as u16)).is_ok())
+            .fold(0u128, |mask, b| mask | (1 << b));
+
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs, "// {} start", dir.path().display()).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs).unwrap();
+
+        writeln!(dbcs_rs, "static {}: [[u16; 256]; {}] = [", dialect_name_double, leads.len()).unwrap();
+        for &lead in &leads {
+            writeln!(dbcs_rs, "\t[  // 0x{:02X}", lead).unwrap();
+            for row in double[lead as usize].chunks(16) {
+                write!(dbcs_rs, "\t\t").unwrap();
+                for unicode in row {
+                    write!(dbcs_rs, "0x{:04X}, ", unicode).unwrap();
+                }
+                writeln!(dbcs_rs).unwrap();
+            }
+            writeln!(dbcs_rs, "\t],").unwrap();
+        }
+        writeln!(dbcs_rs, "];").unwrap();
+        writeln!(dbcs_rs).unwrap();
+
+        writeln!(dbcs_rs, "static {}: [(u16, u16); {}] = [", dialect_name_encode, encode.len()).unwrap();
+        for row in encode.chunks(8) {
+            write!(dbcs_rs, "\t").unwrap();
+            for &(unicode, code) in row {
+                write!(dbcs_rs, "(0x{:04X}, 0x{:04X}), ", unicode, code).unwrap();
+            }
+            writeln!(dbcs_rs).unwrap();
+        }
+        writeln!(dbcs_rs, "];").unwrap();
+        writeln!(dbcs_rs).unwrap();
+
+        for line in BufReader::new(File::open(&documentation_md).unwrap()).lines().map(Result::unwrap) {
+            writeln!(dbcs_rs, "/// {}", line).unwrap();
+        }
+        writeln!(dbcs_rs, "pub static {}: DoubleByteCodepage = DoubleByteCodepage {{", dialect_name_type).unwrap();
+        writeln!(dbcs_rs, "\tsingle: [").unwrap();
+        for unicode in single.iter() {
+            writeln!(dbcs_rs, "\t\t'\\u{{{:06X}}}',", unicode.map(|c| c as u32).unwrap_or(0)).unwrap();
+        }
+        writeln!(dbcs_rs, "\t],").unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs, "\toverlap_mask: 0x{:X},", overlap_mask).unwrap();
+        writeln!(dbcs_rs, "\tundefined_mask: [0x{:X}, 0x{:X}],", undefined_mask[0], undefined_mask[1]).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        write!(dbcs_rs, "\tlead_bytes: &[").unwrap();
+        for r in &lead_bytes {
+            write!(dbcs_rs, "0x{:02X}..=0x{:02X}, ", r.first, r.last).unwrap();
+        }
+        writeln!(dbcs_rs, "],").unwrap();
+        writeln!(dbcs_rs, "\tlead_index: [").unwrap();
+        for b in 0..=0xFFu8 {
+            writeln!(dbcs_rs, "\t\t{},", leads.iter().position(|&l| l == b).map(|i| i + 1).unwrap_or(0)).unwrap();
+        }
+        writeln!(dbcs_rs, "\t],").unwrap();
+        writeln!(dbcs_rs, "\tdouble: &{},", dialect_name_double).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs, "\tunicode_to_dbcs: &{},", dialect_name_encode).unwrap();
+        writeln!(dbcs_rs, "}};").unwrap();
+
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs, "// {} end", dir.path().display()).unwrap();
+        writeln!(dbcs_rs).unwrap();
+        writeln!(dbcs_rs).unwrap();
+    }
+
+    let mut transliteration_rs = File::create(PathBuf::from(format!("{}/transliteration.rs", out_dir))).unwrap();
+    let mut transliterated = Vec::new();
+
+    writeln!(transliteration_rs, "fn transliteration_table(unicode: char) -> Option<&'static str> {{").unwrap();
+    writeln!(transliteration_rs, "\tSome(match unicode {{").unwrap();
+    for table in &["decompositions", "punctuation", "lookalikes"] {
+        let table_tsv = PathBuf::from(format!("transliteration-specs/{}.tsv", table));
+        println!("cargo:rerun-if-changed={}", table_tsv.display());
+
+        for Transliteration { unicode, transliteration, comment } in Transliteration::from_transliterations(&table_tsv) {
+            if transliterated.contains(&unicode) {
+                panic!("{}: duplicate transliteration for 0x{:04X}", table_tsv.display(), unicode as u32);
+            }
+            transliterated.push(unicode);
+
+            write!(transliteration_rs, "\t\t\'\\u{{{:06X}}}\' => \"", unicode as u32).unwrap();
+            for c in transliteration.chars() {
+                write!(transliteration_rs, "\\u{{{:06X}}}", c as u32).unwrap();
+            }
+            writeln!(transliteration_rs, "\",  // {}", comment).unwrap();
+        }
+        writeln!(transliteration_rs).unwrap();
     }
+    writeln!(transliteration_rs, "\t\t_ => return None,").unwrap();
+    writeln!(transliteration_rs, "\t}})").unwrap();
+    writeln!(transliteration_rs, "}}").unwrap();
 }
@@ -0,0 +1,9 @@
+#[inline(always)]
+const fn DIALECT_OVERLAP_CP437(b: u8) -> bool {
+    (b > 0x1F && b < 0x60) || (b > 0x60 && b < 0x7B) || b == 0x7C
+}
+
+#[inline(always)]
+const fn DIALECT_OVERLAP_UNICODE(c: char) -> bool {
+    (c > '\u{1F}' && c < '\u{60}') || (c > '\u{60}' && c < '\u{7B}') || c == '\u{7C}'
+}
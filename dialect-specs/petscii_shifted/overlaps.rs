@@ -0,0 +1,9 @@
+#[inline(always)]
+const fn DIALECT_OVERLAP_CP437(b: u8) -> bool {
+    b < 0x41 || b == 0x5B || b == 0x5D
+}
+
+#[inline(always)]
+const fn DIALECT_OVERLAP_UNICODE(c: char) -> bool {
+    c < '\u{41}' || c == '\u{5B}' || c == '\u{5D}'
+}
@@ -0,0 +1,270 @@
+use self::super::SingleByteCodepage;
+use std::ops::RangeInclusive;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::{char, fmt};
+
+
+/// Errors which can occur when building a codepage whose encoding doesn't round-trip its decoding.
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub enum BuildError {
+    /// `unicode` is decoded from, or aliased to, both `first` and `second`, so it can't be encoded back to each of them.
+    Ambiguous { unicode: char, first: u8, second: u8 },
+    /// `cp437` was declared as overlapping, but isn't in the ASCII range, is undefined,
+    /// or doesn't decode to the Unicode codepoint of the same value.
+    Overlap { cp437: u8 },
+    /// `unicode` is aliased to `cp437`, which is undefined, so it can't be decoded back.
+    Undefined { unicode: char, cp437: u8 },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuildError::Ambiguous { unicode, first, second } => {
+                write!(f, "U+{:04X} would encode to both 0x{:02X} and 0x{:02X}", unicode as u32, first, second)
+            }
+            BuildError::Overlap { cp437 } => write!(f, "0x{:02X} declared overlapping, but must be ASCII, defined, and decode to U+{:04X}", cp437, cp437),
+            BuildError::Undefined { unicode, cp437 } => write!(f, "U+{:04X} aliased to undefined 0x{:02X}", unicode as u32, cp437),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BuildError {}
+
+
+/// Builder for custom [`SingleByteCodepage`](struct.SingleByteCodepage.html)s.
+///
+/// Every decode entry is also an encode entry, so that each codepoint round-trips,
+/// save for ones decoding to the same Unicode codepoint, which encodes to whichever of them it's [aliased](#method.alias) to:
+/// [`build()`](#method.build) fails if that can't be satisfied.
+///
+/// # Examples
+///
+/// Start with `CP437_WINGDINGS`, but with `✓` in place of `√` and a couple more ways to get a bullet:
+///
+/// ```
+/// # use codepage_437::{CP437_WINGDINGS, SingleByteCodepageBuilder};
+/// let dialect = SingleByteCodepageBuilder::from_dialect(&CP437_WINGDINGS)
+///     .map(0xFB, '✓')
+///     .alias('√', 0xFB)
+///     .alias('⋅', 0xF9)
+///     .alias('‧', 0xF9)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(dialect.decode(0xFB), '✓');
+/// assert_eq!(dialect.encode('✓'), Some(0xFB));
+/// assert_eq!(dialect.encode('√'), Some(0xFB));
+/// assert_eq!(dialect.encode('‧'), Some(0xF9));
+/// assert_eq!(dialect.encode('A'), Some(0x41));
+/// ```
+///
+/// Inconsistent:
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, SingleByteCodepageBuilder, BuildError};
+/// // 0x41 decodes to 'A', so 'A' can't also encode to 0x80
+/// let error = SingleByteCodepageBuilder::from_dialect(&CP437_CONTROL).alias('A', 0x80).build().unwrap_err();
+/// assert_eq!(error, BuildError::Ambiguous { unicode: 'A', first: 0x41, second: 0x80 });
+/// ```
+#[derive(Debug, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct SingleByteCodepageBuilder {
+    cp437_to_unicode: Vec<char>,
+    /// Unicode, cp437
+    aliases: Vec<(char, u8)>,
+    overlaps: Vec<bool>,
+    undefined: Vec<bool>,
+}
+
+/// The original name of [`SingleByteCodepageBuilder`](struct.SingleByteCodepageBuilder.html).
+pub type Cp437DialectBuilder = SingleByteCodepageBuilder;
+
+impl SingleByteCodepageBuilder {
+    /// Start from scratch, with each codepoint decoding to the Unicode one of the same value, as in ISO-8859-1.
+    ///
+    /// Nothing is declared overlapping, and everything is defined.
+    pub fn new() -> SingleByteCodepageBuilder {
+        SingleByteCodepageBuilder {
+            cp437_to_unicode: (0..=0xFFu8).map(|b| b as char).collect(),
+            aliases: vec![],
+            overlaps: vec![false; 256],
+            undefined: vec![false; 256],
+        }
+    }
+
+    /// Start from the decode table, encode-only aliases, ASCII overlaps, and undefined codepoints of the specified dialect.
+    ///
+    /// Where multiple codepoints decode to the same Unicode one, like `0x14` and `0xF4` in `CP850_WINGDINGS`,
+    /// it's aliased to the one it encodes to, so building without changes converts the same way as the dialect.
+    pub fn from_dialect(dialect: &SingleByteCodepage) -> SingleByteCodepageBuilder {
+        let cp437_to_unicode: Vec<_> = (0..=0xFFu8).map(|b| dialect.decode(b)).collect();
+        let mut aliases = dialect.aliases();
+        aliases.extend((0..=0xFFu8)
+            .filter(|&b| dialect.defined(b) && !dialect.round_trips(b))
+            .filter_map(|b| dialect.encode(dialect.decode(b)).map(|cp437| (dialect.decode(b), cp437))));
+        aliases.sort();
+        aliases.dedup();
+        let overlaps = (0..=0xFFu8).map(|b| dialect.overlap_cp437(b)).collect();
+        let undefined = (0..=0xFFu8).map(|b| !dialect.defined(b)).collect();
+
+        SingleByteCodepageBuilder {
+            cp437_to_unicode,
+            aliases,
+            overlaps,
+            undefined,
+        }
+    }
+
+    /// Decode the specified codepoint to the specified Unicode one, and encode it back.
+    ///
+    /// This defines it, if it was undefined.
+    /// Mapping a codepoint to anything but the Unicode one of the same value stops it overlapping.
+    pub fn map(&mut self, cp437: u8, unicode: char) -> &mut SingleByteCodepageBuilder {
+        self.cp437_to_unicode[cp437 as usize] = unicode;
+        self.undefined[cp437 as usize] = false;
+        if unicode != cp437 as char {
+            self.overlaps[cp437 as usize] = false;
+        }
+        self
+    }
+
+    /// Map the specified codepoint to nothing.
+    ///
+    /// It'll decode to the Unicode codepoint of the same value, fail strict decoding,
+    /// and not be encoded back, nor overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, SingleByteCodepageBuilder};
+    /// let dialect = SingleByteCodepageBuilder::from_dialect(&CP437_CONTROL).undefine(0xFF).build().unwrap();
+    ///
+    /// assert!(!dialect.defined(0xFF));
+    /// assert_eq!(dialect.try_decode(0xFF), None);
+    /// assert_eq!(dialect.encode('\u{A0}'), None);
+    /// ```
+    pub fn undefine(&mut self, cp437: u8) -> &mut SingleByteCodepageBuilder {
+        self.cp437_to_unicode[cp437 as usize] = cp437 as char;
+        self.undefined[cp437 as usize] = true;
+        self.overlaps[cp437 as usize] = false;
+        self
+    }
+
+    /// Additionally encode the specified Unicode codepoint to the specified one in this codepage, without affecting decoding.
+    ///
+    /// This replaces the previous alias of the Unicode codepoint, if any.
+    /// If multiple codepoints decode to the Unicode one, aliasing it to one of them picks the one it encodes to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{SingleByteCodepageBuilder, BuildError};
+    /// let mut builder = SingleByteCodepageBuilder::new();
+    /// builder.map(0x80, '€').map(0xA4, '€');
+    /// assert_eq!(builder.build(), Err(BuildError::Ambiguous { unicode: '€', first: 0x80, second: 0xA4 }));
+    ///
+    /// let dialect = builder.alias('€', 0xA4).build().unwrap();
+    /// assert_eq!(dialect.decode(0x80), '€');
+    /// assert_eq!(dialect.encode('€'), Some(0xA4));
+    /// ```
+    pub fn alias(&mut self, unicode: char, cp437: u8) -> &mut SingleByteCodepageBuilder {
+        self.unalias(unicode);
+        self.aliases.push((unicode, cp437));
+        self
+    }
+
+    /// Remove the alias of the specified Unicode codepoint, if any.
+    pub fn unalias(&mut self, unicode: char) -> &mut SingleByteCodepageBuilder {
+        self.aliases.retain(|&(c, _)| c != unicode);
+        self
+    }
+
+    /// Declare the specified codepoints to be identical to the Unicode ones of the same value.
+    ///
+    /// This lets conversions of data consisting only thereof borrow it instead of copying,
+    /// so it's only allowed in the ASCII range (`0x00..=0x7F`), where UTF-8 is single-byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{SingleByteCodepageBuilder, ToCodepage};
+    /// # use std::borrow::Cow;
+    /// let dialect = SingleByteCodepageBuilder::new().overlap(0x20..=0x7E).build().unwrap();
+    ///
+    /// assert_eq!("Local news".to_codepage(&dialect), Ok(Cow::Borrowed(&b"Local news"[..])));
+    /// ```
+    pub fn overlap(&mut self, range: RangeInclusive<u8>) -> &mut SingleByteCodepageBuilder {
+        for b in range {
+            self.overlaps[b as usize] = true;
+        }
+        self
+    }
+
+    /// Validate the round-trips and create the codepage.
+    pub fn build(&self) -> Result<SingleByteCodepage, BuildError> {
+        let mut cp437_to_unicode = ['\x00'; 256];
+        cp437_to_unicode.copy_from_slice(&self.cp437_to_unicode);
+
+        let mut unicode_to_cp437: Vec<_> = self.cp437_to_unicode
+            .iter()
+            .enumerate()
+            .filter(|&(b, &c)| !self.undefined[b] && !self.aliased_elsewhere(c, b as u8))
+            .map(|(b, &c)| (c, b as u8))
+            .collect();
+        unicode_to_cp437.sort();
+        if let Some(w) = unicode_to_cp437.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(BuildError::Ambiguous {
+                unicode: w[0].0,
+                first: w[0].1,
+                second: w[1].1,
+            });
+        }
+
+        for &(unicode, cp437) in &self.aliases {
+            if self.undefined[cp437 as usize] {
+                return Err(BuildError::Undefined { unicode, cp437 });
+            }
+            match unicode_to_cp437.binary_search_by_key(&unicode, |&(c, _)| c) {
+                Ok(i) if unicode_to_cp437[i].1 == cp437 => {}
+                Ok(i) => {
+                    return Err(BuildError::Ambiguous {
+                        unicode,
+                        first: unicode_to_cp437[i].1,
+                        second: cp437,
+                    })
+                }
+                Err(i) => unicode_to_cp437.insert(i, (unicode, cp437)),
+            }
+        }
+
+        let mut overlap_mask = 0;
+        for (b, _) in self.overlaps.iter().enumerate().filter(|&(_, &o)| o) {
+            if b >= 0x80 || self.undefined[b] || cp437_to_unicode[b] != b as u8 as char {
+                return Err(BuildError::Overlap { cp437: b as u8 });
+            }
+            overlap_mask |= 1 << b;
+        }
+
+        let mut undefined_mask = [0; 2];
+        for (b, _) in self.undefined.iter().enumerate().filter(|&(_, &u)| u) {
+            undefined_mask[b / 128] |= 1 << (b % 128);
+        }
+
+        Ok(SingleByteCodepage::from_tables(cp437_to_unicode, unicode_to_cp437, overlap_mask, undefined_mask))
+    }
+
+    /// Check, whether the specified Unicode codepoint is aliased to another codepoint, which also decodes to it.
+    fn aliased_elsewhere(&self, unicode: char, cp437: u8) -> bool {
+        self.aliases
+            .iter()
+            .any(|&(c, b)| c == unicode && b != cp437 && !self.undefined[b as usize] && self.cp437_to_unicode[b as usize] == unicode)
+    }
+}
+
+impl Default for SingleByteCodepageBuilder {
+    fn default() -> SingleByteCodepageBuilder {
+        SingleByteCodepageBuilder::new()
+    }
+}
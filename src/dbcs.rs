@@ -0,0 +1,472 @@
+#[cfg(feature = "alloc")]
+use self::super::dialect::overlap_prefix;
+#[cfg(feature = "alloc")]
+use self::super::{DecodeError, CodepageError};
+use std::ops::RangeInclusive;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use std::{char, fmt};
+#[cfg(feature = "alloc")]
+use std::str;
+
+
+/// Specifier for a double-byte codepage, like the East Asian DOS ones.
+///
+/// Codepoints are either a single byte, or a lead byte followed by a trail byte;
+/// the latter are written as `u16`s, with the lead byte in the high byte, like `0x93FA`.
+///
+/// Decoding doesn't map undefined codepoints to anything, since pairs have no Unicode codepoint of the same value:
+/// the conversions replace them with U+FFFD REPLACEMENT CHARACTER, or fail, in their strict (`try_`) variants.
+/// An undefined pair whose trail byte is in the ASCII range only consumes the lead byte,
+/// so that an ASCII character following a stray lead byte is decoded nevertheless.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use codepage_437::{CP932, FromDbcs, ToDbcs};
+/// let cp932 = vec![0x93, 0xFA, 0x96, 0x7B, 0x8C, 0xEA, 0x20, 0x83, 0x65, 0x83, 0x4C, 0x83, 0x58, 0x83, 0x67];
+/// let unicode = "日本語 テキスト";
+///
+/// assert_eq!(String::from_dbcs(cp932.clone(), &CP932), unicode);
+/// assert_eq!(unicode.to_dbcs(&CP932), Ok(cp932.into()));
+///
+/// assert!(CP932.is_lead(0x93));
+/// assert_eq!(CP932.decode(0x93FA), Some('日'));
+/// assert_eq!(CP932.encode('語'), Some(0x8CEA));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DoubleByteCodepage {
+    single: [char; 256],
+
+    /// Bit per ASCII codepoint decoding to and encoding from the Unicode one of the same value
+    overlap_mask: u128,
+    /// Bit per byte not decoding to anything on its own: lead bytes, and undefined single-byte codepoints
+    undefined_mask: [u128; 2],
+
+    lead_bytes: &'static [RangeInclusive<u8>],
+    /// Per byte, 1 + index of the lead byte's table in `double`, or 0 if not a lead byte
+    lead_index: [u8; 256],
+    /// Per lead byte, the Basic Multilingual Plane codepoints each trail byte decodes to, 0 if undefined
+    double: &'static [[u16; 256]],
+
+    /// Unicode, codepoint; sorted by Unicode codepoint
+    unicode_to_dbcs: &'static [(u16, u16)],
+}
+
+impl DoubleByteCodepage {
+    /// Get the ranges lead bytes are in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP932;
+    /// assert_eq!(CP932.lead_bytes(), &[0x81..=0x9F, 0xE0..=0xFC]);
+    /// ```
+    pub fn lead_bytes(&self) -> &'static [RangeInclusive<u8>] {
+        self.lead_bytes
+    }
+
+    /// Check, whether the specified byte starts a two-byte codepoint.
+    #[inline]
+    pub fn is_lead(&self, b: u8) -> bool {
+        self.lead_index[b as usize] != 0
+    }
+
+    /// Decode a single codepoint in this codepage into a Unicode one, if it's defined.
+    ///
+    /// Codepoints up to `0xFF` are single bytes, the rest pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP936;
+    /// assert_eq!(CP936.decode(0x41), Some('A'));
+    /// assert_eq!(CP936.decode(0xD6D0), Some('中'));
+    ///
+    /// assert_eq!(CP936.decode(0xD6), None);    // lead byte
+    /// assert_eq!(CP936.decode(0x4142), None);  // not a lead byte
+    /// ```
+    pub fn decode(&self, code: u16) -> Option<char> {
+        if code <= 0xFF {
+            let b = code as u8;
+            if self.undefined_mask[b as usize / 128] & (1 << (b % 128)) == 0 {
+                Some(self.single[b as usize])
+            } else {
+                None
+            }
+        } else {
+            self.decode_pair((code >> 8) as u8, code as u8)
+        }
+    }
+
+    /// Try to encode a single Unicode codepoint as one in this codepage.
+    #[inline]
+    pub fn encode(&self, unicode: char) -> Option<u16> {
+        if unicode as u32 > 0xFFFF {
+            return None;
+        }
+        self.unicode_to_dbcs.binary_search_by_key(&(unicode as u16), |&(u, _)| u).ok().map(|i| self.unicode_to_dbcs[i].1)
+    }
+
+    /// Decode the specified data in this codepage, appending it to the specified string.
+    ///
+    /// Undefined codepoints, and a lead byte at the end of the data, are replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP949;
+    /// let mut unicode = String::new();
+    /// CP949.decode_into(&[0xC7, 0xD1, 0xB1, 0xDB, 0xC7], &mut unicode);
+    /// assert_eq!(unicode, "한글\u{FFFD}");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode_into(&self, dbcs: &[u8], into: &mut String) {
+        let _ = self.decode_impl(dbcs, true, false, into);
+    }
+
+    /// Encode the specified string as data in this codepage, appending it to the specified buffer.
+    ///
+    /// If that fails, the representable prefix is appended nevertheless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP950, CodepageError};
+    /// let mut cp950 = Vec::new();
+    /// assert_eq!(CP950.encode_into("中文 ₧", &mut cp950),
+    ///            Err(CodepageError { representable_up_to: 7, char_index: 3, unrepresentable: '₧' }));
+    /// assert_eq!(cp950, &[0xA4, 0xA4, 0xA4, 0xE5, 0x20]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encode_into(&self, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        let overlapping = overlap_prefix(self.overlap_mask, unicode.as_bytes());
+        into.reserve(unicode.len());
+        into.extend_from_slice(&unicode.as_bytes()[..overlapping]);
+
+        // Overlapping characters are single bytes
+        for (char_index, (i, c)) in unicode[overlapping..].char_indices().map(|(i, c)| (overlapping + i, c)).enumerate() {
+            match self.encode(c) {
+                Some(code) if code <= 0xFF => into.push(code as u8),
+                Some(code) => into.extend_from_slice(&[(code >> 8) as u8, code as u8]),
+                None => {
+                    return Err(CodepageError {
+                        representable_up_to: i,
+                        char_index: overlapping + char_index,
+                        unrepresentable: c,
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_pair(&self, lead: u8, trail: u8) -> Option<char> {
+        match self.lead_index[lead as usize] {
+            0 => None,
+            i => {
+                match self.double[i as usize - 1][trail as usize] {
+                    0 => None,
+                    unicode => char::from_u32(unicode as u32),
+                }
+            }
+        }
+    }
+
+    /// Decode the first codepoint of the specified non-empty data.
+    #[cfg(feature = "alloc")]
+    fn decode_step(&self, dbcs: &[u8]) -> Step {
+        let b = dbcs[0];
+        if !self.is_lead(b) {
+            match self.decode(b as u16) {
+                Some(c) => Step::Char(c, 1),
+                None => Step::Undefined(1),
+            }
+        } else if dbcs.len() == 1 {
+            Step::Incomplete
+        } else {
+            match self.decode_pair(b, dbcs[1]) {
+                Some(c) => Step::Char(c, 2),
+                // Don't swallow the ASCII character after a stray lead byte
+                None if dbcs[1] < 0x80 => Step::Undefined(1),
+                None => Step::Undefined(2),
+            }
+        }
+    }
+
+    /// Decode the specified data, appending it to the specified string, and returning the amount of bytes decoded.
+    ///
+    /// Undefined codepoints stop decoding if `strict`, and are replaced with U+FFFD REPLACEMENT CHARACTER otherwise;
+    /// a lead byte at the end of the data is left undecoded, unless this is the `last` of it, when it's undefined.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn decode_impl(&self, dbcs: &[u8], last: bool, strict: bool, into: &mut String) -> Result<usize, DecodeError> {
+        let overlapping = overlap_prefix(self.overlap_mask, dbcs);
+        into.reserve(dbcs.len());
+        into.push_str(str::from_utf8(&dbcs[..overlapping]).unwrap());
+
+        let mut read = overlapping;
+        while read < dbcs.len() {
+            let (unicode, len) = match self.decode_step(&dbcs[read..]) {
+                Step::Char(c, len) => (c, len),
+                Step::Incomplete if !last => break,
+                Step::Undefined(_) |
+                Step::Incomplete if strict => {
+                    return Err(DecodeError {
+                        defined_up_to: read,
+                        undefined: dbcs[read],
+                    })
+                }
+                Step::Undefined(len) => (char::REPLACEMENT_CHARACTER, len),
+                Step::Incomplete => (char::REPLACEMENT_CHARACTER, 1),
+            };
+            into.push(unicode);
+            read += len;
+        }
+        Ok(read)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn overlap_dbcs_prefix(&self, dbcs: &[u8]) -> usize {
+        overlap_prefix(self.overlap_mask, dbcs)
+    }
+}
+
+impl fmt::Debug for DoubleByteCodepage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DoubleByteCodepage")
+            .field("lead_bytes", &self.lead_bytes)
+            .field("single", &&self.single[..])
+            .field("overlap_mask", &self.overlap_mask)
+            .field("undefined_mask", &self.undefined_mask)
+            .field("mappings", &self.unicode_to_dbcs.len())
+            .finish()
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+enum Step {
+    /// Character, and the length of its codepoint
+    Char(char, usize),
+    /// Length of the undefined codepoint
+    Undefined(usize),
+    /// Lead byte at the end of the data
+    Incomplete,
+}
+
+
+/// Move data encoded in a double-byte codepage to a Unicode container of the specified type.
+///
+/// This is [`FromCodepage`](trait.FromCodepage.html) for [`DoubleByteCodepage`](struct.DoubleByteCodepage.html)s.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP936, DecodeError, FromDbcs};
+/// let cp936 = vec![0xD6, 0xD0, 0xCE, 0xC4, 0x20, 0x31, 0x30, 0x80];
+/// assert_eq!(String::from_dbcs(cp936, &CP936), "中文 10€");
+///
+/// // The last byte is a lead byte, missing its trail byte
+/// let truncated = vec![0xD6, 0xD0, 0xCE];
+/// assert_eq!(String::from_dbcs(truncated.clone(), &CP936), "中\u{FFFD}");
+/// assert_eq!(String::try_from_dbcs(truncated, &CP936), Err(DecodeError { defined_up_to: 2, undefined: 0xCE }));
+/// ```
+#[cfg(feature = "alloc")]
+pub trait FromDbcs<T: Sized> {
+    /// Do the conversion, replacing undefined codepoints with U+FFFD REPLACEMENT CHARACTER.
+    fn from_dbcs(data: T, dialect: &DoubleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    fn try_from_dbcs(data: T, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+}
+
+#[cfg(feature = "alloc")]
+impl FromDbcs<Vec<u8>> for String {
+    fn from_dbcs(data: Vec<u8>, dialect: &DoubleByteCodepage) -> Self {
+        if dialect.overlap_dbcs_prefix(&data) == data.len() {
+            String::from_utf8(data).unwrap()
+        } else {
+            from_dbcs_slice_impl(&data, dialect)
+        }
+    }
+
+    fn try_from_dbcs(data: Vec<u8>, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        if dialect.overlap_dbcs_prefix(&data) == data.len() {
+            Ok(String::from_utf8(data).unwrap())
+        } else {
+            try_from_dbcs_slice_impl(&data, dialect)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromDbcs<Box<[u8]>> for String {
+    fn from_dbcs(data: Box<[u8]>, dialect: &DoubleByteCodepage) -> Self {
+        String::from_dbcs(data.into_vec(), dialect)
+    }
+
+    fn try_from_dbcs(data: Box<[u8]>, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        String::try_from_dbcs(data.into_vec(), dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c> FromDbcs<Cow<'c, [u8]>> for String {
+    fn from_dbcs(data: Cow<'c, [u8]>, dialect: &DoubleByteCodepage) -> Self {
+        match data {
+            Cow::Borrowed(data) => from_dbcs_slice_impl(data, dialect),
+            Cow::Owned(data) => String::from_dbcs(data, dialect),
+        }
+    }
+
+    fn try_from_dbcs(data: Cow<'c, [u8]>, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        match data {
+            Cow::Borrowed(data) => try_from_dbcs_slice_impl(data, dialect),
+            Cow::Owned(data) => String::try_from_dbcs(data, dialect),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> FromDbcs<[u8; N]> for String {
+    fn from_dbcs(data: [u8; N], dialect: &DoubleByteCodepage) -> Self {
+        from_dbcs_slice_impl(&data, dialect)
+    }
+
+    fn try_from_dbcs(data: [u8; N], dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        try_from_dbcs_slice_impl(&data, dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn from_dbcs_slice_impl(data: &[u8], dialect: &DoubleByteCodepage) -> String {
+    let mut ret = String::new();
+    dialect.decode_into(data, &mut ret);
+    ret
+}
+
+#[cfg(feature = "alloc")]
+fn try_from_dbcs_slice_impl(data: &[u8], dialect: &DoubleByteCodepage) -> Result<String, DecodeError> {
+    let mut ret = String::new();
+    dialect.decode_impl(data, true, true, &mut ret)?;
+    Ok(ret)
+}
+
+
+/// Try to borrow data encoded in a double-byte codepage as a Unicode container of the specified type.
+///
+/// If that cannot be done, clone it.
+///
+/// This is [`BorrowFromCodepage`](trait.BorrowFromCodepage.html) for [`DoubleByteCodepage`](struct.DoubleByteCodepage.html)s.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP950, BorrowFromDbcs};
+/// # use std::borrow::Cow;
+/// let cp950 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0xB7, 0x73, 0xBB, 0x44];
+///
+/// assert_eq!(Cow::borrow_from_dbcs(&cp950[..], &CP950), "Local 新聞");
+/// assert!(match Cow::borrow_from_dbcs(&cp950[..5], &CP950) {
+///     Cow::Borrowed("Local") => true,
+///     _ => false,
+/// });
+/// ```
+#[cfg(feature = "alloc")]
+pub trait BorrowFromDbcs<'c, T: ?Sized> {
+    /// Do the conversion, replacing undefined codepoints with U+FFFD REPLACEMENT CHARACTER.
+    fn borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    fn try_borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromDbcs<'c, T> for Cow<'c, str> {
+    fn borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Self {
+        let data = data.as_ref();
+        if dialect.overlap_dbcs_prefix(data) == data.len() {
+            Cow::Borrowed(str::from_utf8(data).unwrap())
+        } else {
+            Cow::Owned(from_dbcs_slice_impl(data, dialect))
+        }
+    }
+
+    fn try_borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        let data = data.as_ref();
+        if dialect.overlap_dbcs_prefix(data) == data.len() {
+            Ok(Cow::Borrowed(str::from_utf8(data).unwrap()))
+        } else {
+            try_from_dbcs_slice_impl(data, dialect).map(Cow::Owned)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromDbcs<'c, T> for String {
+    fn borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Self {
+        from_dbcs_slice_impl(data.as_ref(), dialect)
+    }
+
+    fn try_borrow_from_dbcs(data: &'c T, dialect: &DoubleByteCodepage) -> Result<Self, DecodeError> {
+        try_from_dbcs_slice_impl(data.as_ref(), dialect)
+    }
+}
+
+
+/// Borrow (if possible) Unicode data as data in a double-byte codepage.
+///
+/// This is [`ToCodepage`](trait.ToCodepage.html) for [`DoubleByteCodepage`](struct.DoubleByteCodepage.html)s;
+/// the error's `char_index` is not the length of the encoded prefix, since characters can take two bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP949, ToDbcs};
+/// assert_eq!("한글 ASCII".to_dbcs(&CP949), Ok(vec![0xC7, 0xD1, 0xB1, 0xDB, 0x20, 0x41, 0x53, 0x43, 0x49, 0x49].into()));
+///
+/// let error = "한글 ₧".to_dbcs(&CP949).unwrap_err();
+/// assert_eq!((error.representable_up_to, error.char_index), (7, 3));
+/// ```
+#[cfg(feature = "alloc")]
+pub trait ToDbcs<'s, T> {
+    /// Do the conversion.
+    fn to_dbcs(&'s self, dialect: &DoubleByteCodepage) -> Result<T, CodepageError>;
+}
+
+#[cfg(feature = "alloc")]
+impl<'s> ToDbcs<'s, Cow<'s, [u8]>> for str {
+    fn to_dbcs(&'s self, dialect: &DoubleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        to_dbcs_cow_impl(self, dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, S: AsRef<str>> ToDbcs<'s, Cow<'s, [u8]>> for S {
+    fn to_dbcs(&'s self, dialect: &DoubleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        to_dbcs_cow_impl(self.as_ref(), dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn to_dbcs_cow_impl<'c>(whom: &'c str, dialect: &DoubleByteCodepage) -> Result<Cow<'c, [u8]>, CodepageError> {
+    if dialect.overlap_dbcs_prefix(whom.as_bytes()) == whom.len() {
+        Ok(Cow::Borrowed(whom.as_bytes()))
+    } else {
+        let mut result = Vec::new();
+        dialect.encode_into(whom, &mut result)?;
+        Ok(Cow::Owned(result))
+    }
+}
+
+
+include!(concat!(env!("OUT_DIR"), "/dbcs.rs"));
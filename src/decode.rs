@@ -1,11 +1,209 @@
-use self::super::Cp437Dialect;
-use std::iter::FromIterator;
-use std::borrow::Cow;
-use std::str;
+use self::super::SingleByteCodepage;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::{char, fmt, str};
+
+
+/// Errors which can occur when strictly decoding data in a single-byte codepage, which contains undefined codepoints.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP1252, DecodeError, FromCodepage};
+/// // 0x81 is undefined in cp1252
+/// let cp1252 = vec![0x43, 0x61, 0x66, 0xE9, 0x81];
+///
+/// let error = String::try_from_codepage(cp1252, &CP1252).unwrap_err();
+/// assert_eq!(error, DecodeError { defined_up_to: 4, undefined: 0x81 });
+/// assert_eq!(error.to_string(), "codepoint 0x81 at index 4 undefined in codepage");
+/// ```
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct DecodeError {
+    /// The index in the given data up to which only defined codepoints were found,
+    /// i.e. the byte offset of the undefined codepoint.
+    pub defined_up_to: usize,
+    /// The undefined codepoint.
+    pub undefined: u8,
+}
+
+impl DecodeError {
+    /// Returns the index in the given data up to which valid data in the codepage was verified.
+    ///
+    /// This is [`defined_up_to`](#structfield.defined_up_to), named after `str::Utf8Error::valid_up_to()`.
+    pub fn valid_up_to(&self) -> usize {
+        self.defined_up_to
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "codepoint 0x{:02X} at index {} undefined in codepage", self.undefined, self.defined_up_to)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {}
+
+
+/// Move data encoded in a single-byte codepage to a Unicode container of the specified type.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, FromCodepage};
+/// let cp850 = vec![0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x3A, 0x20,
+///                  0x9D, 0x72, 0x65, 0x73, 0x75, 0x6E, 0x64, 0x20, 0x9B, 0x6C, 0x20, 0x9C, 0x35];
+/// let unicode = "Local news: Øresund øl £5";
+///
+/// assert_eq!(String::from_codepage(cp850, &CP850_CONTROL), unicode);  // cp850 is moved out of
+/// ```
+pub trait FromCodepage<T: Sized> {
+    /// Do the conversion.
+    ///
+    /// Undefined codepoints decode to the Unicode ones of the same value, as with
+    /// [`SingleByteCodepage::decode()`](struct.SingleByteCodepage.html#method.decode).
+    fn from_codepage(data: T, dialect: &SingleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP1252, DecodeError, FromCodepage};
+    /// assert_eq!(String::try_from_codepage(vec![0x80, 0x35], &CP1252), Ok("€5".to_string()));
+    /// assert_eq!(String::try_from_codepage(vec![0x80, 0x8D, 0x35], &CP1252),
+    ///            Err(DecodeError { defined_up_to: 1, undefined: 0x8D }));
+    /// ```
+    fn try_from_codepage(data: T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+
+    /// Do the conversion, decoding undefined codepoints to U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP1252, FromCodepage};
+    /// assert_eq!(String::from_codepage_lossy(vec![0x80, 0x8D, 0x35], &CP1252), "€\u{FFFD}5");
+    /// ```
+    fn from_codepage_lossy(data: T, dialect: &SingleByteCodepage) -> Self;
+}
+
+impl FromCodepage<Vec<u8>> for String {
+    fn from_codepage(data: Vec<u8>, dialect: &SingleByteCodepage) -> Self {
+        let overlapping = dialect.overlap_cp437_prefix(&data);
+        if overlapping == data.len() {
+            String::from_utf8(data).unwrap()
+        } else {
+            decode_after_prefix(&data, overlapping, dialect)
+        }
+    }
+
+    fn try_from_codepage(data: Vec<u8>, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        check_defined(&data, dialect)?;
+        Ok(String::from_codepage(data, dialect))
+    }
+
+    fn from_codepage_lossy(data: Vec<u8>, dialect: &SingleByteCodepage) -> Self {
+        if check_defined(&data, dialect).is_ok() {
+            String::from_codepage(data, dialect)
+        } else {
+            from_codepage_lossy_slice_impl(&data, dialect)
+        }
+    }
+}
+
+impl FromCodepage<Box<[u8]>> for String {
+    fn from_codepage(data: Box<[u8]>, dialect: &SingleByteCodepage) -> Self {
+        String::from_codepage(data.into_vec(), dialect)
+    }
+
+    fn try_from_codepage(data: Box<[u8]>, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        String::try_from_codepage(data.into_vec(), dialect)
+    }
+
+    fn from_codepage_lossy(data: Box<[u8]>, dialect: &SingleByteCodepage) -> Self {
+        String::from_codepage_lossy(data.into_vec(), dialect)
+    }
+}
+
+impl<'c> FromCodepage<Cow<'c, [u8]>> for String {
+    fn from_codepage(data: Cow<'c, [u8]>, dialect: &SingleByteCodepage) -> Self {
+        match data {
+            Cow::Borrowed(data) => from_codepage_slice_impl(data, dialect),
+            Cow::Owned(data) => String::from_codepage(data, dialect),
+        }
+    }
+
+    fn try_from_codepage(data: Cow<'c, [u8]>, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        match data {
+            Cow::Borrowed(data) => try_from_codepage_slice_impl(data, dialect),
+            Cow::Owned(data) => String::try_from_codepage(data, dialect),
+        }
+    }
+
+    fn from_codepage_lossy(data: Cow<'c, [u8]>, dialect: &SingleByteCodepage) -> Self {
+        match data {
+            Cow::Borrowed(data) => from_codepage_lossy_slice_impl(data, dialect),
+            Cow::Owned(data) => String::from_codepage_lossy(data, dialect),
+        }
+    }
+}
+
+/// The buffer is reused if it's not shared with any other `Bytes`.
+#[cfg(feature = "bytes")]
+impl FromCodepage<Bytes> for String {
+    fn from_codepage(data: Bytes, dialect: &SingleByteCodepage) -> Self {
+        String::from_codepage(Vec::from(data), dialect)
+    }
+
+    fn try_from_codepage(data: Bytes, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        String::try_from_codepage(Vec::from(data), dialect)
+    }
+
+    fn from_codepage_lossy(data: Bytes, dialect: &SingleByteCodepage) -> Self {
+        String::from_codepage_lossy(Vec::from(data), dialect)
+    }
+}
+
+impl<const N: usize> FromCodepage<[u8; N]> for String {
+    fn from_codepage(data: [u8; N], dialect: &SingleByteCodepage) -> Self {
+        from_codepage_slice_impl(&data, dialect)
+    }
+
+    fn try_from_codepage(data: [u8; N], dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        try_from_codepage_slice_impl(&data, dialect)
+    }
+
+    fn from_codepage_lossy(data: [u8; N], dialect: &SingleByteCodepage) -> Self {
+        from_codepage_lossy_slice_impl(&data, dialect)
+    }
+}
+
+fn from_codepage_slice_impl(data: &[u8], dialect: &SingleByteCodepage) -> String {
+    let overlapping = dialect.overlap_cp437_prefix(data);
+    decode_after_prefix(data, overlapping, dialect)
+}
+
+fn try_from_codepage_slice_impl(data: &[u8], dialect: &SingleByteCodepage) -> Result<String, DecodeError> {
+    check_defined(data, dialect)?;
+    Ok(from_codepage_slice_impl(data, dialect))
+}
+
+fn from_codepage_lossy_slice_impl(data: &[u8], dialect: &SingleByteCodepage) -> String {
+    let overlapping = dialect.overlap_cp437_prefix(data);
+    decode_lossy_after_prefix(data, overlapping, dialect)
+}
 
 
 /// Move data encoded in cp437 to a Unicode container of the specified type.
 ///
+/// This is [`FromCodepage`](trait.FromCodepage.html) under its original name, and is implemented for everything that implements it.
+///
 /// # Examples
 ///
 /// ```
@@ -24,53 +222,143 @@ use std::str;
 /// ```
 pub trait FromCp437<T: Sized> {
     /// Do the conversion.
-    fn from_cp437(cp437: T, dialect: &Cp437Dialect) -> Self;
+    fn from_cp437(cp437: T, dialect: &SingleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    fn try_from_cp437(cp437: T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+
+    /// Do the conversion, decoding undefined codepoints to U+FFFD REPLACEMENT CHARACTER.
+    fn from_cp437_lossy(cp437: T, dialect: &SingleByteCodepage) -> Self;
 }
 
-macro_rules! from_cp437_slice_impl {
-    ($($l:expr)*) => ($(
-        impl FromCp437<[u8; $l]> for String {
-            fn from_cp437(cp437: [u8; $l], dialect: &Cp437Dialect) -> Self {
-                from_cp437_slice_impl(&cp437, dialect)
-            }
-        }
-    )*)
+impl<T, S: FromCodepage<T>> FromCp437<T> for S {
+    fn from_cp437(cp437: T, dialect: &SingleByteCodepage) -> Self {
+        S::from_codepage(cp437, dialect)
+    }
+
+    fn try_from_cp437(cp437: T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        S::try_from_codepage(cp437, dialect)
+    }
+
+    fn from_cp437_lossy(cp437: T, dialect: &SingleByteCodepage) -> Self {
+        S::from_codepage_lossy(cp437, dialect)
+    }
 }
 
-impl FromCp437<Vec<u8>> for String {
-    fn from_cp437(cp437: Vec<u8>, dialect: &Cp437Dialect) -> Self {
-        if cp437.iter().all(|&b| dialect.overlap_cp437(b)) {
-            String::from_utf8(cp437).unwrap()
-        } else {
-            String::from_iter(cp437.into_iter().map(|b| dialect.decode(b)))
-        }
+
+/// Try to borrow data encoded in a single-byte codepage as a Unicode container of the specified type.
+///
+/// If that cannot be done, clone it.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, BorrowFromCodepage};
+/// # use std::borrow::Cow;
+/// let cp850 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x3A, 0x20,
+///              0x9D, 0x72, 0x65, 0x73, 0x75, 0x6E, 0x64, 0x20, 0x9B, 0x6C, 0x20, 0x9C, 0x35];
+/// let unicode = "Local news: Øresund øl £5";
+///
+/// assert_eq!(Cow::borrow_from_codepage(&cp850[..], &CP850_CONTROL),
+///            String::borrow_from_codepage(&cp850[..], &CP850_CONTROL));
+/// assert_eq!(Cow::borrow_from_codepage(&cp850[..], &CP850_CONTROL), unicode);
+///
+/// assert!(match Cow::borrow_from_codepage(&cp850[..10], &CP850_CONTROL) {
+///     Cow::Borrowed("Local news") => true,
+///     _ => false,
+/// });
+/// ```
+pub trait BorrowFromCodepage<'c, T: ?Sized> {
+    /// Do the conversion.
+    ///
+    /// Undefined codepoints decode to the Unicode ones of the same value, as with
+    /// [`SingleByteCodepage::decode()`](struct.SingleByteCodepage.html#method.decode).
+    fn borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP1252, DecodeError, BorrowFromCodepage};
+    /// # use std::borrow::Cow;
+    /// assert_eq!(Cow::try_borrow_from_codepage(&b"Caf\xE9"[..], &CP1252), Ok(Cow::Borrowed("Café")));
+    /// assert_eq!(Cow::try_borrow_from_codepage(&b"Caf\x81"[..], &CP1252),
+    ///            Err(DecodeError { defined_up_to: 3, undefined: 0x81 }));
+    /// ```
+    fn try_borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+
+    /// Do the conversion, decoding undefined codepoints to U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP1252, BorrowFromCodepage};
+    /// # use std::borrow::Cow;
+    /// assert_eq!(Cow::borrow_from_codepage_lossy(&b"Caf\x81"[..], &CP1252), "Caf\u{FFFD}");
+    /// ```
+    fn borrow_from_codepage_lossy(data: &'c T, dialect: &SingleByteCodepage) -> Self;
+}
+
+impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromCodepage<'c, T> for Cow<'c, str> {
+    fn borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Self {
+        borrow_from_codepage_cow_slice_impl(data.as_ref(), dialect)
+    }
+
+    fn try_borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        check_defined(data.as_ref(), dialect)?;
+        Ok(borrow_from_codepage_cow_slice_impl(data.as_ref(), dialect))
+    }
+
+    fn borrow_from_codepage_lossy(data: &'c T, dialect: &SingleByteCodepage) -> Self {
+        borrow_from_codepage_lossy_cow_slice_impl(data.as_ref(), dialect)
     }
 }
 
-impl FromCp437<[u8; 0]> for String {
-    fn from_cp437(_: [u8; 0], _: &Cp437Dialect) -> Self {
-        String::new()
+impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromCodepage<'c, T> for String {
+    fn borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Self {
+        borrow_from_codepage_string_slice_impl(data.as_ref(), dialect)
+    }
+
+    fn try_borrow_from_codepage(data: &'c T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        try_from_codepage_slice_impl(data.as_ref(), dialect)
+    }
+
+    fn borrow_from_codepage_lossy(data: &'c T, dialect: &SingleByteCodepage) -> Self {
+        from_codepage_lossy_slice_impl(data.as_ref(), dialect)
     }
 }
 
-from_cp437_slice_impl!(    1  2  3  4  5  6  7  8  9
-                       10 11 12 13 14 15 16 17 18 19
-                       20 21 22 23 24 25 26 27 28 29
-                       30 31 32);
+fn borrow_from_codepage_cow_slice_impl<'c>(data: &'c [u8], dialect: &SingleByteCodepage) -> Cow<'c, str> {
+    let overlapping = dialect.overlap_cp437_prefix(data);
+    if overlapping == data.len() {
+        Cow::Borrowed(str::from_utf8(data).unwrap())
+    } else {
+        Cow::Owned(decode_after_prefix(data, overlapping, dialect))
+    }
+}
 
-fn from_cp437_slice_impl(cp437: &[u8], dialect: &Cp437Dialect) -> String {
-    if cp437.iter().all(|&b| dialect.overlap_cp437(b)) {
-        String::from_utf8(cp437.to_vec()).unwrap()
+fn borrow_from_codepage_lossy_cow_slice_impl<'c>(data: &'c [u8], dialect: &SingleByteCodepage) -> Cow<'c, str> {
+    let overlapping = dialect.overlap_cp437_prefix(data);
+    if overlapping == data.len() {
+        Cow::Borrowed(str::from_utf8(data).unwrap())
     } else {
-        String::from_iter(cp437.iter().map(|&b| dialect.decode(b)))
+        Cow::Owned(decode_lossy_after_prefix(data, overlapping, dialect))
     }
 }
 
+fn borrow_from_codepage_string_slice_impl(data: &[u8], dialect: &SingleByteCodepage) -> String {
+    let overlapping = dialect.overlap_cp437_prefix(data);
+    decode_after_prefix(data, overlapping, dialect)
+}
+
 
 /// Try to borrow data encoded in cp437 as a Unicode container of the specified type.
 ///
 /// If that cannot be done, clone it.
 ///
+/// This is [`BorrowFromCodepage`](trait.BorrowFromCodepage.html) under its original name, and is implemented for everything that implements it.
+///
 /// # Examples
 ///
 /// ```
@@ -92,33 +380,55 @@ fn from_cp437_slice_impl(cp437: &[u8], dialect: &Cp437Dialect) -> String {
 /// ```
 pub trait BorrowFromCp437<'c, T: ?Sized> {
     /// Do the conversion.
-    fn borrow_from_cp437(cp437: &'c T, dialect: &Cp437Dialect) -> Self;
+    fn borrow_from_cp437(cp437: &'c T, dialect: &SingleByteCodepage) -> Self;
+
+    /// Do the conversion, failing on the first undefined codepoint.
+    fn try_borrow_from_cp437(cp437: &'c T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> where Self: Sized;
+
+    /// Do the conversion, decoding undefined codepoints to U+FFFD REPLACEMENT CHARACTER.
+    fn borrow_from_cp437_lossy(cp437: &'c T, dialect: &SingleByteCodepage) -> Self;
 }
 
-impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromCp437<'c, T> for Cow<'c, str> {
-    fn borrow_from_cp437(cp437: &'c T, dialect: &Cp437Dialect) -> Self {
-        borrow_from_cp437_cow_slice_impl(cp437.as_ref(), dialect)
+impl<'c, T: ?Sized, S: BorrowFromCodepage<'c, T>> BorrowFromCp437<'c, T> for S {
+    fn borrow_from_cp437(cp437: &'c T, dialect: &SingleByteCodepage) -> Self {
+        S::borrow_from_codepage(cp437, dialect)
     }
-}
 
-impl<'c, T: AsRef<[u8]> + ?Sized> BorrowFromCp437<'c, T> for String {
-    fn borrow_from_cp437(cp437: &'c T, dialect: &Cp437Dialect) -> Self {
-        borrow_from_cp437_string_slice_impl(cp437.as_ref(), dialect)
+    fn try_borrow_from_cp437(cp437: &'c T, dialect: &SingleByteCodepage) -> Result<Self, DecodeError> {
+        S::try_borrow_from_codepage(cp437, dialect)
     }
-}
 
-fn borrow_from_cp437_cow_slice_impl<'c>(cp437: &'c [u8], dialect: &Cp437Dialect) -> Cow<'c, str> {
-    if cp437.iter().all(|&b| dialect.overlap_cp437(b)) {
-        Cow::Borrowed(str::from_utf8(&cp437[..]).unwrap())
-    } else {
-        Cow::Owned(String::from_iter(cp437.iter().map(|&b| dialect.decode(b))))
+    fn borrow_from_cp437_lossy(cp437: &'c T, dialect: &SingleByteCodepage) -> Self {
+        S::borrow_from_codepage_lossy(cp437, dialect)
     }
 }
 
-fn borrow_from_cp437_string_slice_impl(cp437: &[u8], dialect: &Cp437Dialect) -> String {
-    if cp437.iter().all(|&b| dialect.overlap_cp437(b)) {
-        String::from_utf8(cp437.to_vec()).unwrap()
-    } else {
-        String::from_iter(cp437.iter().map(|&b| dialect.decode(b)))
+
+/// Decode the specified data, the first `overlapping` bytes of which are known to overlap, and so are copied verbatim.
+fn decode_after_prefix(data: &[u8], overlapping: usize, dialect: &SingleByteCodepage) -> String {
+    let mut ret = String::with_capacity(overlapping + (data.len() - overlapping) * 3);
+    ret.push_str(str::from_utf8(&data[..overlapping]).unwrap());
+    ret.extend(dialect.decode_iter(data[overlapping..].iter().cloned()));
+    ret
+}
+
+/// Like `decode_after_prefix()`, but undefined codepoints decode to U+FFFD REPLACEMENT CHARACTER.
+fn decode_lossy_after_prefix(data: &[u8], overlapping: usize, dialect: &SingleByteCodepage) -> String {
+    let mut ret = String::with_capacity(overlapping + (data.len() - overlapping) * 3);
+    ret.push_str(str::from_utf8(&data[..overlapping]).unwrap());
+    ret.extend(data[overlapping..].iter().map(|&b| dialect.try_decode(b).unwrap_or(char::REPLACEMENT_CHARACTER)));
+    ret
+}
+
+/// Find the first undefined codepoint in the specified data, if any.
+fn check_defined(data: &[u8], dialect: &SingleByteCodepage) -> Result<(), DecodeError> {
+    match data.iter().position(|&b| !dialect.defined(b)) {
+        Some(i) => {
+            Err(DecodeError {
+                defined_up_to: i,
+                undefined: data[i],
+            })
+        }
+        None => Ok(()),
     }
 }
@@ -1,50 +1,627 @@
+#[cfg(feature = "std")]
+use self::super::mapping::{MappingError, parse_mapping};
+#[cfg(feature = "alloc")]
+use self::super::CodepageDiff;
+use self::super::{CodepageError, Unrepresentable, DecodeIter, EncodeIter, Mappings};
 use std::hash::{Hasher, Hash};
-use std::borrow::Cow;
-use std::{cmp, fmt};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Read;
+use std::{cmp, fmt, str};
 
 
-/// Specifier for the specific kind of cp437.
+/// Tables owned by runtime-constructed codepages, or only ever borrowed from the generated ones without `alloc`.
+#[cfg(feature = "alloc")]
+type Table<T> = Cow<'static, [T]>;
+#[cfg(not(feature = "alloc"))]
+type Table<T> = &'static [T];
+
+#[cfg(feature = "alloc")]
+macro_rules! static_table {
+    ($table:expr) => {
+        Cow::Borrowed($table)
+    };
+}
+#[cfg(not(feature = "alloc"))]
+macro_rules! static_table {
+    ($table:expr) => {
+        $table
+    };
+}
+
+
+/// Specifier for a single-byte codepage, like a specific kind of cp437.
 ///
 /// Dialects are instances of this type, aggregating data necessary to perform conversions.
 #[derive(Clone)]
-pub struct Cp437Dialect {
+pub struct SingleByteCodepage {
     cp437_to_unicode: [char; 256],
 
-    overlap_unicode: fn(unicode: char) -> bool,
-    overlap_cp437: fn(cp437: u8) -> bool,
+    /// Bit per ASCII codepoint decoding to and encoding from the Unicode one of the same value
+    overlap_mask: u128,
+    /// Bit per codepoint mapped to nothing, which decodes to the Unicode one of the same value, but isn't encoded to
+    undefined_mask: [u128; 2],
 
     encode: fn(unicode: char) -> Option<u8>,
+    /// Sorted by Unicode codepoint, consulted before `encode`; remaps are inserted here
+    unicode_to_cp437: Table<(char, u8)>,
+    /// Open-addressed hash table of `unicode_to_cp437`, derived therefrom, see `encode_index()`
+    encode_index: Table<u32>,
+
+    /// Unicode, cp437; encoded by `encode`, but not decoded from the codepoint they're encoded to
+    aliases: Table<(char, u8)>,
+    /// Sorted by Unicode codepoint, from the comments in the generated dialects' specs
+    names: &'static [(char, &'static str)],
 
     /// cp437, from, to
-    remaps: Cow<'static, [(u8, char, char)]>,
+    remaps: Table<(u8, char, char)>,
 }
 
-impl Cp437Dialect {
-    /// Check, whether the specified Unicode codepoint overlaps with a cp437 one.
+/// The original name of [`SingleByteCodepage`](struct.SingleByteCodepage.html), from back when only cp437 was supported.
+pub type Cp437Dialect = SingleByteCodepage;
+
+impl SingleByteCodepage {
+    /// Load a codepage from a mapping file.
+    ///
+    /// Both the format used by the Unicode Consortium (like
+    /// [`CP437.TXT`](http://www.unicode.org/Public/MAPPINGS/VENDORS/MICSFT/PC/CP437.TXT))
+    /// and the one of this crate's `dialect-specs` (a tab-separated header, codepage code, Unicode code, and comment)
+    /// are accepted.
+    ///
+    /// Codes explicitly mapped to nothing (with a `#UNDEFINED` comment or an empty Unicode column) are undefined.
+    /// Codes not mapped at all decode to the Unicode codepoint of the same value;
+    /// of those, only the ones in the ASCII range can also be encoded.
+    /// Where multiple codes map to the same Unicode codepoint, it encodes to the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::SingleByteCodepage;
+    /// let mapping = "#    Name:     cp1252 to Unicode table (excerpt)\n\
+    ///                0x41\t0x0041\t#LATIN CAPITAL LETTER A\n\
+    ///                0x80\t0x20AC\t#EURO SIGN\n\
+    ///                0x81\t      \t#UNDEFINED\n";
+    ///
+    /// let dialect = SingleByteCodepage::from_mapping_reader(mapping.as_bytes()).unwrap();
+    /// assert_eq!(dialect.decode(0x80), '€');
+    /// assert_eq!(dialect.encode('€'), Some(0x80));
+    /// assert_eq!(dialect.encode('B'), Some(0x42));
+    /// assert_eq!(dialect.encode('\u{81}'), None);
+    /// assert!(!dialect.defined(0x81));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_mapping_reader<R: Read>(reader: R) -> Result<SingleByteCodepage, MappingError> {
+        let entries = parse_mapping(reader)?;
+
+        let mut cp437_to_unicode = ['\x00'; 256];
+        let mut mapped = [false; 256];
+        let mut undefined_mask = [0; 2];
+        for (i, entry) in cp437_to_unicode.iter_mut().enumerate() {
+            *entry = i as u8 as char;
+        }
+        for &(cp437, unicode) in &entries {
+            match unicode {
+                Some(unicode) => cp437_to_unicode[cp437 as usize] = unicode,
+                None => undefined_mask[cp437 as usize / 128] |= 1 << (cp437 % 128),
+            }
+            mapped[cp437 as usize] = true;
+        }
+
+        let mut unicode_to_cp437: Vec<_> = (0..0x80u8).filter(|&b| !mapped[b as usize]).map(|b| (b as char, b)).collect();
+        unicode_to_cp437.extend(entries.iter().filter_map(|&(cp437, unicode)| unicode.map(|unicode| (unicode, cp437))));
+        // Stable sort of the reversed table so that the last mapping of a codepoint comes first and survives the dedup
+        unicode_to_cp437.reverse();
+        unicode_to_cp437.sort_by_key(|&(unicode, _)| unicode);
+        unicode_to_cp437.dedup_by_key(|&mut (unicode, _)| unicode);
+
+        let mut ret = SingleByteCodepage::from_tables(cp437_to_unicode, unicode_to_cp437, 0, undefined_mask);
+        ret.overlap_mask = (0..0x80u8)
+            .filter(|&b| ret.defined(b) && ret.decode(b) == b as char && ret.encode(b as char) == Some(b))
+            .fold(0, |mask, b| mask | (1 << b));
+        Ok(ret)
+    }
+
+    /// Create a table-driven codepage, with the specified encode table sorted by Unicode codepoint,
+    /// overlap bit-mask for the ASCII range, and undefined bit-mask.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_tables(cp437_to_unicode: [char; 256], unicode_to_cp437: Vec<(char, u8)>, overlap_mask: u128,
+                              undefined_mask: [u128; 2])
+                              -> SingleByteCodepage {
+        SingleByteCodepage {
+            cp437_to_unicode,
+
+            overlap_mask,
+            undefined_mask,
+
+            encode: encode_none,
+            encode_index: Cow::Owned(encode_index(&unicode_to_cp437)),
+            unicode_to_cp437: Cow::Owned(unicode_to_cp437),
+
+            aliases: Cow::Borrowed(&[]),
+            names: &[],
+
+            remaps: Cow::Borrowed(&[]),
+        }
+    }
+
+    /// Check, whether the specified Unicode codepoint overlaps with one in this codepage.
     #[inline]
     pub fn overlap_unicode(&self, unicode: char) -> bool {
-        (self.overlap_unicode)(unicode) && !self.remaps.iter().rev().find(|&&(_, _, to)| to == unicode).is_some()
+        (unicode as u32) < 0x80 && self.overlap_mask & (1 << unicode as u32) != 0
     }
 
-    /// Check, whether the specified cp437 codepoint overlaps with a Unicode one.
+    /// Check, whether the specified codepoint in this codepage overlaps with a Unicode one.
     #[inline]
     pub fn overlap_cp437(&self, cp437: u8) -> bool {
-        (self.overlap_cp437)(cp437) && !self.remaps.iter().rev().find(|&&(whom, _, _)| whom == cp437).is_some()
+        cp437 < 0x80 && self.overlap_mask & (1 << cp437) != 0
+    }
+
+    /// Get the length of the longest prefix of the specified data in this codepage consisting solely of overlapping codepoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// assert_eq!(CP437_CONTROL.overlap_cp437_prefix(&[0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x9E, 0xAB]), 6);
+    /// ```
+    #[inline]
+    pub fn overlap_cp437_prefix(&self, cp437: &[u8]) -> usize {
+        overlap_prefix(self.overlap_mask, cp437)
+    }
+
+    /// Get the length, in bytes, of the longest prefix of the specified string consisting solely of overlapping codepoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// assert_eq!(CP437_CONTROL.overlap_unicode_prefix("Local ₧½"), 6);
+    /// ```
+    #[inline]
+    pub fn overlap_unicode_prefix(&self, unicode: &str) -> usize {
+        // Overlaps are all ASCII, so they're the same in UTF-8, and a non-ASCII byte always starts a non-overlapping character
+        overlap_prefix(self.overlap_mask, unicode.as_bytes())
     }
 
-    /// Decode a single cp437 codepoint into a Unicode one.
+    /// Check, whether the specified codepoint in this codepage is mapped to anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP1252;
+    /// assert!(CP1252.defined(0x80));
+    /// assert!(!CP1252.defined(0x81));
+    /// ```
+    #[inline]
+    pub fn defined(&self, cp437: u8) -> bool {
+        self.undefined_mask[cp437 as usize / 128] & (1 << (cp437 % 128)) == 0
+    }
+
+    /// Decode a single codepoint in this codepage into a Unicode one.
+    ///
+    /// Undefined codepoints decode to the Unicode codepoint of the same value
+    /// (for `0x80..=0x9F`, a C1 control), like on Windows, but don't encode back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP1252;
+    /// assert_eq!(CP1252.decode(0x80), '€');
+    ///
+    /// assert_eq!(CP1252.decode(0x81), '\u{81}');
+    /// assert_eq!(CP1252.encode('\u{81}'), None);
+    /// ```
     #[inline(always)]
     pub fn decode(&self, cp437: u8) -> char {
         self.cp437_to_unicode[cp437 as usize]
     }
 
-    /// Try to encode a single Unicode codepoint as a cp437 one.
+    /// Decode a single codepoint in this codepage into a Unicode one, if it's defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP1252;
+    /// assert_eq!(CP1252.try_decode(0x80), Some('€'));
+    /// assert_eq!(CP1252.try_decode(0x81), None);
+    /// ```
+    #[inline]
+    pub fn try_decode(&self, cp437: u8) -> Option<char> {
+        if self.defined(cp437) {
+            Some(self.decode(cp437))
+        } else {
+            None
+        }
+    }
+
+    /// Try to encode a single Unicode codepoint as one in this codepage.
     #[inline]
     pub fn encode(&self, unicode: char) -> Option<u8> {
-        self.remaps.iter().rev().find(|&&(_, _, to)| to == unicode).map(|&(whom, _, _)| whom).or_else(|| (self.encode)(unicode))
+        if !self.encode_index.is_empty() {
+            let mask = self.encode_index.len() - 1;
+            let mut i = encode_index_hash(unicode, mask);
+            loop {
+                match self.encode_index[i] {
+                    ENCODE_INDEX_EMPTY => break,
+                    entry if entry >> 8 == unicode as u32 => return Some(entry as u8),
+                    _ => i = (i + 1) & mask,
+                }
+            }
+        }
+
+        (self.encode)(unicode)
+    }
+
+    /// Check, whether the specified codepoint in this codepage encodes back to itself after being decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, CP850_WINGDINGS};
+    /// assert!(CP437_CONTROL.round_trips(0x14));
+    ///
+    /// // Both 0x14 and 0xF4 decode to ¶, which encodes to 0xF4
+    /// assert!(!CP850_WINGDINGS.round_trips(0x14));
+    /// assert!(CP850_WINGDINGS.round_trips(0xF4));
+    /// ```
+    #[inline]
+    pub fn round_trips(&self, cp437: u8) -> bool {
+        self.encode(self.decode(cp437)) == Some(cp437)
+    }
+
+    /// Get all codepoints in this codepage alongside the Unicode ones they decode to, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_WINGDINGS;
+    /// let chart: String = CP437_WINGDINGS.mappings().skip(0xB0).take(3).map(|(_, c)| c).collect();
+    /// assert_eq!(chart, "░▒▓");
+    ///
+    /// assert_eq!(CP437_WINGDINGS.mappings().len(), 256);
+    /// ```
+    pub fn mappings<'d>(&'d self) -> Mappings<'d> {
+        Mappings::new(self)
+    }
+
+    /// Get the Unicode codepoints additionally encoded to codepoints in this codepage, which decode to something else,
+    /// alongside those codepoints, sorted.
+    ///
+    /// These are the variants of the built-in dialects, the aliases of built ones,
+    /// and the Unicode codepoints replaced by remaps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_WINGDINGS;
+    /// let aliases = CP437_WINGDINGS.aliases();
+    /// assert!(aliases.contains(&('✓', 0xFB)));
+    /// assert_eq!(CP437_WINGDINGS.decode(0xFB), '√');
+    ///
+    /// let mut mapping = CP437_WINGDINGS.clone();
+    /// mapping.remap(0xFB, '✓');
+    /// assert!(mapping.aliases().contains(&('√', 0xFB)));
+    /// assert!(!mapping.aliases().contains(&('✓', 0xFB)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn aliases(&self) -> Vec<(char, u8)> {
+        let mut ret: Vec<_> = self.aliases
+            .iter()
+            .cloned()
+            .chain(self.unicode_to_cp437.iter().cloned())
+            .chain(self.remaps.iter().map(|&(cp437, from, _)| (from, cp437)))
+            .filter(|&(unicode, cp437)| self.decode(cp437) != unicode && self.encode(unicode) == Some(cp437))
+            .collect();
+        ret.sort();
+        ret.dedup();
+        ret
+    }
+
+    /// Get the remaps applied to this codepage, in order: the codepoint, the Unicode one it used to decode to, and the one it decodes to now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use codepage_437::CP437_WINGDINGS;
+    /// let mut mapping = CP437_WINGDINGS.clone();
+    /// mapping.remap(0xFB, '✓');
+    /// assert_eq!(mapping.remaps(), [(0xFB, '√', '✓')]);
+    /// # }
+    /// ```
+    pub fn remaps(&self) -> &[(u8, char, char)] {
+        #[cfg(feature = "alloc")]
+        let remaps = &self.remaps;
+        #[cfg(not(feature = "alloc"))]
+        let remaps = self.remaps;
+        remaps
+    }
+
+    /// Get the name of the specified Unicode codepoint, as given in the specs of the built-in dialects.
+    ///
+    /// Only codepoints decoded from, or aliased to, ones in the built-in dialects are named,
+    /// and only by those dialects and ones derived from them by remapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// assert_eq!(CP437_CONTROL.name(CP437_CONTROL.decode(0x9E)), Some("PESETA SIGN"));
+    /// assert_eq!(CP437_CONTROL.name('✓'), Some("check mark"));
+    /// assert_eq!(CP437_CONTROL.name('A'), None);  // Overlapping ASCII is implied, not specified
+    /// ```
+    pub fn name(&self, unicode: char) -> Option<&'static str> {
+        self.names.binary_search_by_key(&unicode, |&(c, _)| c).ok().map(|i| self.names[i].1)
+    }
+
+    /// Find what the specified codepage decodes and encodes differently from this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, CP437_WINGDINGS};
+    /// let diff = CP437_CONTROL.diff(&CP437_WINGDINGS);
+    /// assert_eq!(diff.decode[0], (0x01, '\x01', '☺'));
+    /// assert_eq!(diff.encode[0], ('\x01', Some(0x01), None));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn diff(&self, other: &SingleByteCodepage) -> CodepageDiff {
+        CodepageDiff::new(self, other)
+    }
+
+    /// Decode the specified codepoints in this codepage into Unicode ones, lazily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let unicode: String = CP437_CONTROL.decode_iter(vec![0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E]).collect();
+    /// assert_eq!(unicode, "₧½ million");
+    /// ```
+    pub fn decode_iter<'d, I: IntoIterator<Item = u8>>(&'d self, cp437: I) -> DecodeIter<'d, I::IntoIter> {
+        DecodeIter::new(self, cp437.into_iter())
+    }
+
+    /// Encode the specified Unicode codepoints as ones in this codepage, lazily.
+    ///
+    /// Each unrepresentable character yields an error, with indices counted from the start of the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let mut cp437 = CP437_CONTROL.encode_iter("₧ż".chars());
+    /// assert_eq!(cp437.next(), Some(Ok(0x9E)));
+    /// assert_eq!(cp437.next().unwrap().unwrap_err().unrepresentable, 'ż');
+    /// assert_eq!(cp437.next(), None);
+    /// ```
+    pub fn encode_iter<'d, I: IntoIterator<Item = char>>(&'d self, unicode: I) -> EncodeIter<'d, I::IntoIter> {
+        EncodeIter::new(self, unicode.into_iter())
+    }
+
+    /// Find the characters of the specified string unrepresentable in this codepage, alongside their byte indices therein.
+    ///
+    /// Characters are looked up the same way as when encoding, but the search goes on past the first unrepresentable one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let unrepresentable: Vec<_> = CP437_CONTROL.unrepresentable("Jurek je żurek w żupanie.").collect();
+    /// assert_eq!(unrepresentable, [(9, 'ż'), (18, 'ż')]);
+    ///
+    /// assert_eq!(CP437_CONTROL.unrepresentable("Local news ₧½").next(), None);
+    /// ```
+    pub fn unrepresentable<'d, 's>(&'d self, unicode: &'s str) -> Unrepresentable<'d, 's> {
+        Unrepresentable::new(self, unicode)
+    }
+
+    /// Decode the specified data in this codepage into the specified buffer, returning the part thereof written to,
+    /// or `None`, if the buffer is too small.
+    ///
+    /// Four bytes per codepoint always suffice, and three, for dialects decoding only to the Basic Multilingual Plane,
+    /// like all the built-in ones; use [`decode_to_utf8()`](#method.decode_to_utf8) to decode piecewise instead.
+    ///
+    /// This doesn't allocate, and neither needs `std` nor `alloc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let cp437 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x0A,
+    ///              0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E];
+    ///
+    /// let mut buf = [0; 64];
+    /// assert_eq!(CP437_CONTROL.decode_to_slice(&cp437, &mut buf).unwrap(), "Local news\n₧½ million");
+    ///
+    /// assert_eq!(CP437_CONTROL.decode_to_slice(&cp437, &mut buf[..16]), None);
+    /// ```
+    pub fn decode_to_slice<'b>(&self, cp437: &[u8], buf: &'b mut [u8]) -> Option<&'b mut str> {
+        let (read, written) = self.decode_to_utf8(cp437, buf);
+        if read != cp437.len() {
+            return None;
+        }
+
+        Some(str::from_utf8_mut(&mut buf[..written]).unwrap())
+    }
+
+    /// Decode as much of the specified data in this codepage as fits into the specified buffer,
+    /// returning the amount of bytes read therefrom and written thereto.
+    ///
+    /// Only whole characters are written, so `buf[..written]` is always valid UTF-8,
+    /// and decoding can be resumed from `cp437[read..]` with another buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// # use std::str;
+    /// let cp437 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x9E, 0xAB];
+    ///
+    /// let mut buf = [0; 9];
+    /// assert_eq!(CP437_CONTROL.decode_to_utf8(&cp437, &mut buf), (7, 9));  // ½ doesn't fit
+    /// assert_eq!(str::from_utf8(&buf), Ok("Local ₧"));
+    ///
+    /// assert_eq!(CP437_CONTROL.decode_to_utf8(&cp437[7..], &mut buf), (1, 2));
+    /// assert_eq!(str::from_utf8(&buf[..2]), Ok("½"));
+    /// ```
+    pub fn decode_to_utf8(&self, cp437: &[u8], buf: &mut [u8]) -> (usize, usize) {
+        let overlapping = self.overlap_cp437_prefix(&cp437[..cp437.len().min(buf.len())]);
+        buf[..overlapping].copy_from_slice(&cp437[..overlapping]);
+
+        let mut written = overlapping;
+        for (read, &b) in cp437.iter().enumerate().skip(overlapping) {
+            let unicode = self.decode(b);
+            if buf.len() - written < unicode.len_utf8() {
+                return (read, written);
+            }
+            written += unicode.encode_utf8(&mut buf[written..]).len();
+        }
+
+        (cp437.len(), written)
     }
 
-    /// Map the specified cp437 codepoint mapped to the specified unicode character instead.
+    /// Decode the specified data in this codepage, appending it to the specified string.
+    ///
+    /// Reusing the string across conversions saves reallocating it each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let frames = [&[0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73][..],
+    ///               &[0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E][..]];
+    ///
+    /// let mut unicode = String::new();
+    /// for frame in &frames {
+    ///     unicode.clear();
+    ///     CP437_CONTROL.decode_into(frame, &mut unicode);
+    /// }
+    /// assert_eq!(unicode, "₧½ million");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode_into(&self, cp437: &[u8], into: &mut String) {
+        let overlapping = self.overlap_cp437_prefix(cp437);
+        into.reserve(cp437.len());
+        into.push_str(str::from_utf8(&cp437[..overlapping]).unwrap());
+        into.extend(self.decode_iter(cp437[overlapping..].iter().cloned()));
+    }
+
+    /// Encode the specified string as data in this codepage into the specified buffer, returning the part thereof written to,
+    /// or `None`, if the buffer is too small to reach the end of the string, or an unrepresentable character therein.
+    ///
+    /// A byte per character (and, thus, `unicode.len()` bytes) always suffices;
+    /// use [`encode_from_utf8()`](#method.encode_from_utf8) to encode piecewise instead.
+    ///
+    /// This doesn't allocate, and neither needs `std` nor `alloc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, CodepageError};
+    /// let mut buf = [0; 32];
+    /// assert_eq!(CP437_CONTROL.encode_to_slice("Local news\n₧½ million", &mut buf),
+    ///            Ok(Some(&mut [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x0A,
+    ///                          0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E][..])));
+    ///
+    /// assert_eq!(CP437_CONTROL.encode_to_slice("Local news\n₧½ million", &mut buf[..16]), Ok(None));
+    ///
+    /// assert_eq!(CP437_CONTROL.encode_to_slice("Eżektor", &mut buf),
+    ///            Err(CodepageError { representable_up_to: 1, char_index: 1, unrepresentable: 'ż' }));
+    /// ```
+    pub fn encode_to_slice<'b>(&self, unicode: &str, buf: &'b mut [u8]) -> Result<Option<&'b mut [u8]>, CodepageError> {
+        let (read, written) = self.encode_from_utf8(unicode, buf)?;
+        if read != unicode.len() {
+            return Ok(None);
+        }
+
+        Ok(Some(&mut buf[..written]))
+    }
+
+    /// Encode as much of the specified string as fits into the specified buffer as data in this codepage,
+    /// returning the amount of bytes read therefrom and written thereto.
+    ///
+    /// Encoding can be resumed from `unicode[read..]` with another buffer.
+    /// On error, the characters before the unrepresentable one have been written, a byte each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::CP437_CONTROL;
+    /// let mut buf = [0; 7];
+    /// assert_eq!(CP437_CONTROL.encode_from_utf8("Local ₧½", &mut buf), Ok((9, 7)));  // ½ doesn't fit
+    /// assert_eq!(buf, [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x9E]);
+    ///
+    /// assert_eq!(CP437_CONTROL.encode_from_utf8(&"Local ₧½"[9..], &mut buf), Ok((2, 1)));
+    /// assert_eq!(buf[0], 0xAB);
+    /// ```
+    pub fn encode_from_utf8(&self, unicode: &str, buf: &mut [u8]) -> Result<(usize, usize), CodepageError> {
+        // As in overlap_unicode_prefix(), the overlapping prefix is ASCII, so cutting the string mid-character is harmless
+        let overlapping = overlap_prefix(self.overlap_mask, &unicode.as_bytes()[..unicode.len().min(buf.len())]);
+        buf[..overlapping].copy_from_slice(&unicode.as_bytes()[..overlapping]);
+
+        let mut written = overlapping;
+        for (read, c) in unicode[overlapping..].char_indices().map(|(i, c)| (overlapping + i, c)) {
+            if written == buf.len() {
+                return Ok((read, written));
+            }
+            buf[written] = self.encode(c).ok_or(CodepageError {
+                    representable_up_to: read,
+                    char_index: written,
+                    unrepresentable: c,
+                })?;
+            written += 1;
+        }
+
+        Ok((unicode.len(), written))
+    }
+
+    /// Encode the specified string as data in this codepage, appending it to the specified buffer.
+    ///
+    /// Reusing the buffer across conversions saves reallocating it each time.
+    /// On error, the characters before the unrepresentable one have been appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, CodepageError};
+    /// let mut cp437 = Vec::new();
+    /// for frame in &["Local news", "₧½ million"] {
+    ///     cp437.clear();
+    ///     CP437_CONTROL.encode_into(frame, &mut cp437).unwrap();
+    /// }
+    /// assert_eq!(cp437, [0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E]);
+    ///
+    /// cp437.clear();
+    /// assert_eq!(CP437_CONTROL.encode_into("Eżektor", &mut cp437).map_err(|e| e.unrepresentable), Err('ż'));
+    /// assert_eq!(cp437, b"E");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encode_into(&self, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        let start = into.len();
+        let overlapping = self.overlap_unicode_prefix(unicode);
+        into.reserve(unicode.len());
+        into.extend_from_slice(&unicode.as_bytes()[..overlapping]);
+
+        for (i, c) in unicode[overlapping..].char_indices() {
+            into.push(self.encode(c).ok_or(CodepageError {
+                    representable_up_to: overlapping + i,
+                    char_index: into.len() - start,
+                    unrepresentable: c,
+                })?);
+        }
+
+        Ok(())
+    }
+
+    /// Map the specified codepoint in this codepage to the specified unicode character instead.
+    ///
+    /// This defines it, if it was undefined.
     ///
     /// # Examples
     ///
@@ -58,64 +635,193 @@ impl Cp437Dialect {
     /// mapping.remap(square_root_or_checkmark, '✓');
     /// assert_eq!(mapping.decode(square_root_or_checkmark), '✓');
     /// ```
-    pub fn remap(&mut self, cp437: u8, unicode: char) -> &mut Cp437Dialect {
+    #[cfg(feature = "alloc")]
+    pub fn remap(&mut self, cp437: u8, unicode: char) -> &mut SingleByteCodepage {
         self.remaps.to_mut().push((cp437, self.cp437_to_unicode[cp437 as usize], unicode));
         self.cp437_to_unicode[cp437 as usize] = unicode;
+        self.undefined_mask[cp437 as usize / 128] &= !(1 << (cp437 % 128));
+
+        {
+            let unicode_to_cp437 = self.unicode_to_cp437.to_mut();
+            match unicode_to_cp437.binary_search_by_key(&unicode, |&(c, _)| c) {
+                Ok(i) => unicode_to_cp437[i].1 = cp437,
+                Err(i) => unicode_to_cp437.insert(i, (unicode, cp437)),
+            }
+        }
+        self.encode_index = Cow::Owned(encode_index(&self.unicode_to_cp437));
+
+        if cp437 < 0x80 {
+            self.overlap_mask &= !(1 << cp437);
+        }
+        if (unicode as u32) < 0x80 {
+            self.overlap_mask &= !(1 << unicode as u32);
+        }
+
         self
     }
+
+    /// Get a version of this codepage, wherein every codepoint round-trips,
+    /// so that any data decoded with it encodes back to exactly the same bytes.
+    ///
+    /// Codepoints that [don't](#method.round_trips) are [remapped](#method.remap)
+    /// to the Private Use Area character at `U+F700` plus their value;
+    /// the rest, as well as encoding any other Unicode character, are unaffected.
+    ///
+    /// The round-trip can't be guaranteed for codepages, that already decode to the `U+F700`-`U+F7FF` range themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP850_WINGDINGS, FromCp437, ToCp437};
+    /// let raw = CP850_WINGDINGS.round_trip();
+    /// assert_eq!(raw.decode(0x14), '\u{F714}');
+    /// assert_eq!(raw.decode(0xF4), '¶');
+    /// assert_eq!(raw.encode('¶'), Some(0xF4));
+    ///
+    /// let data: Vec<u8> = (0..=0xFF).collect();
+    /// assert_eq!(String::from_cp437(data.clone(), &raw).to_cp437(&raw), Ok(data.into()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn round_trip(&self) -> SingleByteCodepage {
+        let mut ret = self.clone();
+        for cp437 in (0..=0xFFu8).filter(|&b| !self.round_trips(b)) {
+            ret.remap(cp437, char::from_u32(ROUND_TRIP_BASE + cp437 as u32).unwrap());
+        }
+        ret
+    }
 }
 
+/// Start of the Private Use Area range non-round-tripping codepoints are remapped to by `round_trip()`.
+#[cfg(feature = "alloc")]
+const ROUND_TRIP_BASE: u32 = 0xF700;
+
 // These traits are implemented manually, because rustc is at a loss for big arrays (like the 256 one).
-impl fmt::Debug for Cp437Dialect {
+impl fmt::Debug for SingleByteCodepage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Cp437Dialect")
+        f.debug_struct("SingleByteCodepage")
             .field("cp437_to_unicode", &&self.cp437_to_unicode[..])
-            .field("overlap_unicode", &self.overlap_unicode)
-            .field("overlap_cp437", &self.overlap_cp437)
+            .field("overlap_mask", &self.overlap_mask)
+            .field("undefined_mask", &self.undefined_mask)
             .field("encode", &self.encode)
+            .field("unicode_to_cp437", &self.unicode_to_cp437)
+            .field("aliases", &self.aliases)
+            .field("names", &self.names)
             .field("remaps", &self.remaps)
             .finish()
     }
 }
 
-impl Hash for Cp437Dialect {
+impl Hash for SingleByteCodepage {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.cp437_to_unicode[..].hash(state);
-        self.overlap_unicode.hash(state);
-        self.overlap_cp437.hash(state);
+        self.overlap_mask.hash(state);
+        self.undefined_mask.hash(state);
         self.encode.hash(state);
+        self.unicode_to_cp437.hash(state);
+        self.aliases.hash(state);
+        self.names.hash(state);
         self.remaps.hash(state);
     }
 }
 
-impl cmp::Eq for Cp437Dialect {}
+impl cmp::Eq for SingleByteCodepage {}
 
-impl cmp::PartialEq for Cp437Dialect {
-    fn eq(&self, other: &Cp437Dialect) -> bool {
-        self.cp437_to_unicode[..] == other.cp437_to_unicode[..] &&  // align
-        self.overlap_unicode == other.overlap_unicode &&            // align
-        self.overlap_cp437 == other.overlap_cp437 &&                // align
-        self.encode == other.encode &&                              // align
+impl cmp::PartialEq for SingleByteCodepage {
+    fn eq(&self, other: &SingleByteCodepage) -> bool {
+        self.cp437_to_unicode[..] == other.cp437_to_unicode[..] &&          // align
+        self.overlap_mask == other.overlap_mask &&                          // align
+        self.undefined_mask == other.undefined_mask &&                      // align
+        self.encode as usize == other.encode as usize &&                    // align
+        self.unicode_to_cp437 == other.unicode_to_cp437 &&                  // align
+        self.aliases == other.aliases &&                                    // align
+        self.names == other.names &&                                        // align
         self.remaps == other.remaps
     }
 }
 
-impl cmp::Ord for Cp437Dialect {
-    fn cmp(&self, other: &Cp437Dialect) -> cmp::Ordering {
+impl cmp::Ord for SingleByteCodepage {
+    fn cmp(&self, other: &SingleByteCodepage) -> cmp::Ordering {
         self.cp437_to_unicode[..]
             .cmp(&other.cp437_to_unicode[..])
-            .then(self.overlap_unicode.cmp(&other.overlap_unicode))
-            .then(self.overlap_cp437.cmp(&other.overlap_cp437))
-            .then(self.encode.cmp(&other.encode))
-            .then(self.remaps.cmp(&other.remaps))
+            .then(self.overlap_mask.cmp(&other.overlap_mask))
+            .then(self.undefined_mask.cmp(&other.undefined_mask))
+            .then((self.encode as usize).cmp(&(other.encode as usize)))
+            // Spelled out, as without alloc the tables are references themselves
+            .then(Ord::cmp(&self.unicode_to_cp437, &other.unicode_to_cp437))
+            .then(Ord::cmp(&self.aliases, &other.aliases))
+            .then(self.names.cmp(other.names))
+            .then(Ord::cmp(&self.remaps, &other.remaps))
     }
 }
 
-impl cmp::PartialOrd for Cp437Dialect {
-    fn partial_cmp(&self, other: &Cp437Dialect) -> Option<cmp::Ordering> {
+impl cmp::PartialOrd for SingleByteCodepage {
+    fn partial_cmp(&self, other: &SingleByteCodepage) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 
+// Encoding for table-driven dialects, which can't have a generated one.
+#[cfg(feature = "alloc")]
+fn encode_none(_: char) -> Option<u8> {
+    None
+}
+
+
+const ALL_ASCII: u128 = !0;
+const WORD_HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+pub(crate) fn overlap_prefix(overlap_mask: u128, data: &[u8]) -> usize {
+    // Where nothing overlaps, like in EBCDIC, there's no prefix to look for
+    if overlap_mask == 0 {
+        return 0;
+    }
+
+    let mut checked = 0;
+
+    // Where all of ASCII overlaps, eight bytes at a time can be checked for having their high bits unset
+    if overlap_mask == ALL_ASCII {
+        let mut word = [0; 8];
+        for chunk in data.chunks_exact(8) {
+            word.copy_from_slice(chunk);
+            if u64::from_ne_bytes(word) & WORD_HIGH_BITS != 0 {
+                break;
+            }
+            checked += 8;
+        }
+    }
+
+    checked + data[checked..].iter().position(|&b| b >= 0x80 || overlap_mask & (1 << b) == 0).unwrap_or(data.len() - checked)
+}
+
+
+const ENCODE_INDEX_EMPTY: u32 = 0xFFFF_FFFF;
+
+/// Hash the specified encode table into a power-of-two-sized one, at most half full, with linear probing;
+/// each entry is the Unicode codepoint shifted left by 8, ORed with the codepoint in the codepage.
+#[cfg(feature = "alloc")]
+fn encode_index(unicode_to_cp437: &[(char, u8)]) -> Vec<u32> {
+    if unicode_to_cp437.is_empty() {
+        return vec![];
+    }
+
+    let mut index = vec![ENCODE_INDEX_EMPTY; (unicode_to_cp437.len() * 2).next_power_of_two()];
+    let mask = index.len() - 1;
+    for &(unicode, cp437) in unicode_to_cp437 {
+        let mut i = encode_index_hash(unicode, mask);
+        while index[i] != ENCODE_INDEX_EMPTY {
+            i = (i + 1) & mask;
+        }
+        index[i] = ((unicode as u32) << 8) | cp437 as u32;
+    }
+    index
+}
+
+/// Fibonacci hashing: the high bits of the product are the well-mixed ones, so take as many of them as `mask` has.
+#[inline(always)]
+fn encode_index_hash(unicode: char, mask: usize) -> usize {
+    ((unicode as u32).wrapping_mul(0x9E37_79B9) >> (mask as u32).leading_zeros()) as usize
+}
+
+
 include!(concat!(env!("OUT_DIR"), "/dialects.rs"));
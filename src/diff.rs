@@ -0,0 +1,113 @@
+use self::super::SingleByteCodepage;
+use alloc::vec::Vec;
+use std::fmt;
+
+
+/// Differences between what two single-byte codepages decode and encode.
+///
+/// Codepages that compare equal never differ, but ones that don't can still convert the same way,
+/// like a built-in dialect and one built from it without changes.
+///
+/// Returned by [`SingleByteCodepage::diff()`](struct.SingleByteCodepage.html#method.diff).
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::CP437_WINGDINGS;
+/// let mut mapping = CP437_WINGDINGS.clone();
+/// mapping.remap(0xFB, '✓');
+///
+/// let diff = CP437_WINGDINGS.diff(&mapping);
+/// assert_eq!(diff.decode, [(0xFB, '√', '✓')]);
+/// assert_eq!(diff.encode, []);  // √ and ✓ both still encode to 0xFB
+/// assert_eq!(diff.to_string(),
+///            "byte  this        other\n\
+///             0xFB  U+221A '√'  U+2713 '✓'\n");
+///
+/// assert!(CP437_WINGDINGS.diff(&CP437_WINGDINGS).is_empty());
+/// ```
+#[derive(Debug, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct CodepageDiff {
+    /// Codepoints decoding differently, in order: the codepoint, the Unicode one it decodes to in this codepage, and in the other.
+    pub decode: Vec<(u8, char, char)>,
+    /// Unicode codepoints encoding differently, sorted: the Unicode codepoint,
+    /// the codepoint it encodes to in this codepage, and in the other, if any.
+    pub encode: Vec<(char, Option<u8>, Option<u8>)>,
+}
+
+impl CodepageDiff {
+    /// Compare the specified codepages.
+    pub(crate) fn new(this: &SingleByteCodepage, other: &SingleByteCodepage) -> CodepageDiff {
+        let decode = this.mappings()
+            .zip(other.mappings())
+            .filter(|&((_, this_unicode), (_, other_unicode))| this_unicode != other_unicode)
+            .map(|((cp437, this_unicode), (_, other_unicode))| (cp437, this_unicode, other_unicode))
+            .collect();
+
+        // Every encodable Unicode codepoint is either decoded from the codepoint it encodes to, or an alias thereof
+        let mut candidates: Vec<_> = this.mappings()
+            .chain(other.mappings())
+            .map(|(_, unicode)| unicode)
+            .chain(this.aliases().into_iter().chain(other.aliases()).map(|(unicode, _)| unicode))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        let encode = candidates.into_iter()
+            .map(|unicode| (unicode, this.encode(unicode), other.encode(unicode)))
+            .filter(|&(_, this_cp437, other_cp437)| this_cp437 != other_cp437)
+            .collect();
+
+        CodepageDiff { decode, encode }
+    }
+
+    /// Check, whether the codepages convert the same way.
+    pub fn is_empty(&self) -> bool {
+        self.decode.is_empty() && self.encode.is_empty()
+    }
+}
+
+/// One table for each of the decode and encode differences, if any, separated by an empty line.
+impl fmt::Display for CodepageDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.decode.is_empty() {
+            writeln!(f, "byte  {:<10}  other", "this")?;
+            for &(cp437, this_unicode, other_unicode) in &self.decode {
+                writeln!(f, "0x{:02X}  {:<10}  {}", cp437, UnicodeCell(this_unicode), UnicodeCell(other_unicode))?;
+            }
+        }
+
+        if !self.encode.is_empty() {
+            if !self.decode.is_empty() {
+                writeln!(f)?;
+            }
+            writeln!(f, "{:<10}  this  other", "char")?;
+            for &(unicode, this_cp437, other_cp437) in &self.encode {
+                writeln!(f, "{:<10}  {:<4}  {}", UnicodeCell(unicode), Cp437Cell(this_cp437), Cp437Cell(other_cp437))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+/// `U+XXXX 'c'`.
+struct UnicodeCell(char);
+
+impl fmt::Display for UnicodeCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("U+{:04X} {:?}", self.0 as u32, self.0))
+    }
+}
+
+/// `0xXX`, or `-` if unrepresentable.
+struct Cp437Cell(Option<u8>);
+
+impl fmt::Display for Cp437Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(cp437) => f.pad(&format!("0x{:02X}", cp437)),
+            None => f.pad("-"),
+        }
+    }
+}
@@ -1,19 +1,55 @@
-use self::super::Cp437Dialect;
-use std::borrow::Cow;
+use self::super::SingleByteCodepage;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "smartstring")]
+use smartstring::{SmartStringMode, SmartString};
+#[cfg(feature = "compact_str")]
+use compact_str::CompactString;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::str::CharIndices;
+use std::fmt;
 
 
-/// Errors which can occur when attempting to interpret a string as a sequence of cp437 codepoints.
+/// Errors which can occur when attempting to interpret a string as a sequence of codepoints in a single-byte codepage.
 ///
-/// As such, the `into_cp437` family of functions and functions make use of this error, for example.
+/// As such, the `into_codepage` family of functions and functions make use of this error, for example.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use codepage_437::{CP437_CONTROL, CodepageError, ToCodepage};
+/// // é and ░ are in cp437, ż isn't
+/// let unicode = "Café ░ żurek";
+///
+/// let error = unicode.to_codepage(&CP437_CONTROL).unwrap_err();
+/// assert_eq!(error, CodepageError { representable_up_to: 10, char_index: 7, unrepresentable: 'ż' });
+/// assert_eq!(&unicode[..error.valid_up_to()], "Café ░ ");
+/// assert_eq!(error.to_string(), "character 'ż' (U+017C) at index 10 not representable in codepage");
+/// # }
+/// ```
 #[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
-pub struct Cp437Error {
-    /// Returns the index in the given string up to which valid cp437 was verified.
+pub struct CodepageError {
+    /// Returns the index in the given string up to which valid data in the codepage was verified.
     ///
-    /// It is the maximum index such that `input[..index].to_cp_437()` would return `Ok(_)`.
+    /// It is the maximum index such that `input[..index].to_cp_437()` would return `Ok(_)`,
+    /// i.e. the byte index of the unrepresentable character.
     ///
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "alloc")] {
     /// # use codepage_437::{CP437_CONTROL, ToCp437};
     /// // some unrepresentable characters, in a &str
     /// let word = "Eżektor";
@@ -23,21 +59,53 @@ pub struct Cp437Error {
     ///
     /// // the second character is unrepresentable here
     /// assert_eq!(error.representable_up_to, 1);
+    /// # }
     /// ```
     pub representable_up_to: usize,
+    /// The index of the unrepresentable character in the given string, counted in characters.
+    ///
+    /// In single-byte codepages, every character before it is encoded as a single byte, so this is also the length of their encoding.
+    pub char_index: usize,
+    /// The unrepresentable character.
+    pub unrepresentable: char,
+}
+
+impl CodepageError {
+    /// Returns the index in the given string up to which valid data in the codepage was verified.
+    ///
+    /// This is [`representable_up_to`](#structfield.representable_up_to), named after `str::Utf8Error::valid_up_to()`.
+    pub fn valid_up_to(&self) -> usize {
+        self.representable_up_to
+    }
+}
+
+impl fmt::Display for CodepageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "character {:?} (U+{:04X}) at index {} not representable in codepage",
+               self.unrepresentable,
+               self.unrepresentable as u32,
+               self.representable_up_to)
+    }
 }
 
-/// A possible error value when converting a `String` into a cp437 byte vector.
+#[cfg(feature = "std")]
+impl Error for CodepageError {}
+
+/// The original name of [`CodepageError`](struct.CodepageError.html).
+pub type Cp437Error = CodepageError;
+
+/// A possible error value when converting a `String` into a byte vector in a single-byte codepage.
 ///
-/// This type is the error type for the [`into_cp437()`](trait.IntoCp437.html#tymethod.into_cp437)
-/// function on [`IntoCp437`](trait.IntoCp437.html). It is designed in such a way to carefully avoid reallocations:
+/// This type is the error type for the [`into_codepage()`](trait.IntoCodepage.html#tymethod.into_codepage)
+/// function on [`IntoCodepage`](trait.IntoCodepage.html). It is designed in such a way to carefully avoid reallocations:
 /// the [`into_string()`](#method.into_string) function will give back the String that was used
 /// in the conversion attempt.
 ///
-/// The [`Cp437Error`](struct.Cp437Error.html) type represents an error that may
+/// The [`CodepageError`](struct.CodepageError.html) type represents an error that may
 /// occur when converting a `&str` to a sequence of `u8`s. In this sense, it's
-/// an analogue to `IntoCp437Error`, and you can get one from a `IntoCp437Error`
-/// through the [`cp437_error()`](#method.cp437_error) function.
+/// an analogue to `IntoCodepageError`, and you can get one from a `IntoCodepageError`
+/// through the [`codepage_error()`](#method.codepage_error) function.
 ///
 /// # Examples
 ///
@@ -51,14 +119,17 @@ pub struct Cp437Error {
 /// assert!(value.is_err());
 /// assert_eq!(value.unwrap_err().into_string(), "Eżektor".to_string());
 /// ```
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
-pub struct IntoCp437Error {
+pub struct IntoCodepageError {
     string: String,
-    error: Cp437Error,
+    error: CodepageError,
+    unrepresentable: Vec<(usize, char)>,
 }
 
-impl IntoCp437Error {
-    /// Returns a `&str` that was attempted to convert to cp437.
+#[cfg(feature = "alloc")]
+impl IntoCodepageError {
+    /// Returns a `&str` that was attempted to convert to the codepage.
     ///
     /// # Examples
     ///
@@ -75,7 +146,7 @@ impl IntoCp437Error {
         &self.string
     }
 
-    /// Returns the `String` that was attempted to convert to cp437.
+    /// Returns the `String` that was attempted to convert to the codepage.
     ///
     /// This function is carefully constructed to avoid allocation. It will
     /// consume the error, moving out the string, so that a copy of the string
@@ -96,16 +167,36 @@ impl IntoCp437Error {
         self.string
     }
 
-    /// Fetch a `Cp437Error` to get more details about the conversion failure.
+    /// Fetch a `CodepageError` to get more details about the conversion failure.
     ///
-    /// The [`Cp437Error`](struct.Cp437Error.html) type represents an error that may
+    /// The [`CodepageError`](struct.CodepageError.html) type represents an error that may
     /// occur when converting a `&str` to a sequence of `u8`s. In this sense, it's
-    /// an analogue to `IntoCp437Error`. See its documentation for more details
+    /// an analogue to `IntoCodepageError`. See its documentation for more details
     /// on using it.
     ///
     /// # Examples
     ///
     /// ```
+    /// # use codepage_437::{CP850_CONTROL, IntoCodepage};
+    /// // some unrepresentable chracters, in a String
+    /// let word = "Eżektor".to_string();
+    ///
+    /// let error = word.into_codepage(&CP850_CONTROL).unwrap_err().codepage_error();
+    ///
+    /// // the first character is unrepresentable here
+    /// assert_eq!(error.representable_up_to, 1);
+    /// ```
+    pub fn codepage_error(&self) -> CodepageError {
+        self.error
+    }
+
+    /// Fetch a `CodepageError` to get more details about the conversion failure.
+    ///
+    /// This is [`codepage_error()`](#method.codepage_error) under its original name.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// # use codepage_437::{CP437_CONTROL, IntoCp437};
     /// // some unrepresentable chracters, in a String
     /// let word = "Eżektor".to_string();
@@ -115,14 +206,273 @@ impl IntoCp437Error {
     /// // the first character is unrepresentable here
     /// assert_eq!(error.representable_up_to, 1);
     /// ```
-    pub fn cp437_error(&self) -> Cp437Error {
+    pub fn cp437_error(&self) -> CodepageError {
         self.error
     }
+
+    /// Returns every character of the string unrepresentable in the codepage, alongside its byte index therein.
+    ///
+    /// The first one is the one described by [`codepage_error()`](#method.codepage_error).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, IntoCodepage};
+    /// let error = "Jurek je żurek w żupanie.".to_string().into_codepage(&CP437_CONTROL).unwrap_err();
+    ///
+    /// assert_eq!(error.unrepresentable(), [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    pub fn unrepresentable(&self) -> &[(usize, char)] {
+        &self.unrepresentable
+    }
+}
+
+/// The original name of [`IntoCodepageError`](struct.IntoCodepageError.html).
+#[cfg(feature = "alloc")]
+pub type IntoCp437Error = IntoCodepageError;
+
+
+/// Iterator over the characters of a string unrepresentable in a single-byte codepage, alongside their byte indices therein.
+///
+/// Returned by [`SingleByteCodepage::unrepresentable()`](struct.SingleByteCodepage.html#method.unrepresentable).
+#[derive(Debug, Clone)]
+pub struct Unrepresentable<'d, 's> {
+    dialect: &'d SingleByteCodepage,
+    offset: usize,
+    chars: CharIndices<'s>,
+}
+
+impl<'d, 's> Unrepresentable<'d, 's> {
+    pub(crate) fn new(dialect: &'d SingleByteCodepage, unicode: &'s str) -> Unrepresentable<'d, 's> {
+        // Overlapping characters are representable by definition, so skip over them in bulk
+        let offset = dialect.overlap_unicode_prefix(unicode);
+        Unrepresentable {
+            dialect,
+            offset,
+            chars: unicode[offset..].char_indices(),
+        }
+    }
+}
+
+impl<'d, 's> Iterator for Unrepresentable<'d, 's> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        let (dialect, offset) = (self.dialect, self.offset);
+        self.chars.find(|&(_, c)| dialect.encode(c).is_none()).map(|(i, c)| (offset + i, c))
+    }
+}
+
+
+/// What to do with characters unrepresentable in the codepage during a lossy conversion.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, ReplacementPolicy, ToCp437};
+/// let unicode = "Jurek je żurek w żupanie.";
+///
+/// assert_eq!(unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(b'?')).encoded,
+///            &b"Jurek je ?urek w ?upanie."[..]);
+/// assert_eq!(unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Skip).encoded,
+///            &b"Jurek je urek w upanie."[..]);
+/// assert_eq!(unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::With(&mut |c| if c == 'ż' { Some(vec![b'z']) } else { None }))
+///                   .encoded,
+///            &b"Jurek je zurek w zupanie."[..]);
+/// ```
+#[cfg(feature = "alloc")]
+pub enum ReplacementPolicy<'f> {
+    /// Substitute the specified codepoint, like `b'?'` or `0xFE` (■).
+    Substitute(u8),
+    /// Leave the character out.
+    Skip,
+    /// Substitute the data the specified function returns, or leave the character out, if it returns `None`.
+    With(&'f mut dyn FnMut(char) -> Option<Vec<u8>>),
+}
+
+/// Result of a lossy conversion to a single-byte codepage.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct LossyEncoded<T> {
+    /// The converted data.
+    pub encoded: T,
+    /// The characters that were replaced, alongside their byte indices in the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, ReplacementPolicy, ToCp437};
+    /// let result = "Jurek je żurek w żupanie.".to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(0xFE));
+    ///
+    /// assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    pub replaced: Vec<(usize, char)>,
+}
+
+/// The original name of [`LossyEncoded`](struct.LossyEncoded.html).
+#[cfg(feature = "alloc")]
+pub type LossyCp437<T> = LossyEncoded<T>;
+
+
+/// Move Unicode data to a container of data in a single-byte codepage.
+///
+/// # Examples
+///
+/// Good:
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, IntoCodepage};
+/// let cp850 = vec![0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x3A, 0x20,
+///                  0x9D, 0x72, 0x65, 0x73, 0x75, 0x6E, 0x64, 0x20, 0x9B, 0x6C, 0x20, 0x9C, 0x35];
+/// let unicode = "Local news: Øresund øl £5".to_string();
+///
+/// assert_eq!(unicode.into_codepage(&CP850_CONTROL), Ok(cp850));  // unicode is moved out of
+/// ```
+///
+/// Unrepresentable:
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, IntoCodepage};
+/// // Ż cannot be represented in cp850
+/// let unicode = "Jurek je żurek w żupanie.".to_string();
+///
+/// let error = unicode.into_codepage(&CP850_CONTROL).unwrap_err();  // unicode is moved out of
+/// assert_eq!(error.as_str(), "Jurek je żurek w żupanie.");
+/// assert_eq!(error.codepage_error().representable_up_to, 9);
+///
+/// let unicode = error.into_string();                            // unicode now the same as original
+/// # assert_eq!(unicode, "Jurek je żurek w żupanie.");
+/// ```
+#[cfg(feature = "alloc")]
+pub trait IntoCodepage<T> {
+    /// Do the conversion.
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<T, IntoCodepageError>;
+
+    /// Do the conversion, replacing unrepresentable characters according to the specified policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP850_CONTROL, ReplacementPolicy, IntoCodepage};
+    /// let unicode = "Jurek je żurek w żupanie.".to_string();
+    ///
+    /// let result = unicode.into_codepage_lossy(&CP850_CONTROL, ReplacementPolicy::Substitute(b'?'));
+    /// assert_eq!(result.encoded, b"Jurek je ?urek w ?upanie.");
+    /// assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T>;
+}
+
+#[cfg(feature = "alloc")]
+impl IntoCodepage<Vec<u8>> for String {
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<Vec<u8>, IntoCodepageError> {
+        let overlapping = dialect.overlap_unicode_prefix(&self);
+        if overlapping == self.len() {
+            Ok(self.into_bytes())
+        } else {
+            to_codepage_impl_meat(&self, overlapping, dialect).map_err(|e| into_codepage_error(self, e, dialect))
+        }
+    }
+
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+        let overlapping = dialect.overlap_unicode_prefix(&self);
+        if overlapping == self.len() {
+            LossyEncoded {
+                encoded: self.into_bytes(),
+                replaced: vec![],
+            }
+        } else {
+            to_codepage_lossy_impl_meat(&self, overlapping, dialect, policy)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl IntoCodepage<Vec<u8>> for Box<str> {
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<Vec<u8>, IntoCodepageError> {
+        self.into_string().into_codepage(dialect)
+    }
+
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+        self.into_string().into_codepage_lossy(dialect, policy)
+    }
+}
+
+/// The string is only cloned if it's borrowed.
+#[cfg(feature = "alloc")]
+impl<'s> IntoCodepage<Vec<u8>> for Cow<'s, str> {
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<Vec<u8>, IntoCodepageError> {
+        self.into_owned().into_codepage(dialect)
+    }
+
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+        self.into_owned().into_codepage_lossy(dialect, policy)
+    }
+}
+
+macro_rules! into_codepage_shared_impl {
+    ($($ptr:ident)*) => ($(
+        /// The allocation is reused if the string only contains overlapping characters.
+        #[cfg(feature = "alloc")]
+        impl IntoCodepage<$ptr<[u8]>> for $ptr<str> {
+            fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<$ptr<[u8]>, IntoCodepageError> {
+                let overlapping = dialect.overlap_unicode_prefix(&self);
+                if overlapping == self.len() {
+                    Ok(self.into())
+                } else {
+                    to_codepage_impl_meat(&self, overlapping, dialect).map($ptr::from).map_err(|e| into_codepage_error(String::from(&*self), e, dialect))
+                }
+            }
+
+            fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<$ptr<[u8]>> {
+                let overlapping = dialect.overlap_unicode_prefix(&self);
+                if overlapping == self.len() {
+                    LossyEncoded {
+                        encoded: self.into(),
+                        replaced: vec![],
+                    }
+                } else {
+                    let LossyEncoded { encoded, replaced } = to_codepage_lossy_impl_meat(&self, overlapping, dialect, policy);
+                    LossyEncoded {
+                        encoded: encoded.into(),
+                        replaced,
+                    }
+                }
+            }
+        }
+    )*)
+}
+
+into_codepage_shared_impl!(Rc Arc);
+
+#[cfg(feature = "smartstring")]
+impl<M: SmartStringMode> IntoCodepage<Vec<u8>> for SmartString<M> {
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<Vec<u8>, IntoCodepageError> {
+        String::from(self).into_codepage(dialect)
+    }
+
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+        String::from(self).into_codepage_lossy(dialect, policy)
+    }
+}
+
+/// The buffer is reused if the string is heap-allocated.
+#[cfg(feature = "compact_str")]
+impl IntoCodepage<Vec<u8>> for CompactString {
+    fn into_codepage(self, dialect: &SingleByteCodepage) -> Result<Vec<u8>, IntoCodepageError> {
+        self.into_string().into_codepage(dialect)
+    }
+
+    fn into_codepage_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+        self.into_string().into_codepage_lossy(dialect, policy)
+    }
 }
 
 
 /// Move Unicode data to a container of cp437 data.
 ///
+/// This is [`IntoCodepage`](trait.IntoCodepage.html) under its original name, and is implemented for everything that implements it.
+///
 /// # Examples
 ///
 /// Good:
@@ -157,29 +507,110 @@ impl IntoCp437Error {
 /// let unicode = error.into_string();                   // unicode now the same as original
 /// # assert_eq!(unicode, "Jurek je żurek w żupanie.");
 /// ```
+#[cfg(feature = "alloc")]
 pub trait IntoCp437<T> {
     /// Do the conversion.
-    fn into_cp437(self, dialect: &Cp437Dialect) -> Result<T, IntoCp437Error>;
+    fn into_cp437(self, dialect: &SingleByteCodepage) -> Result<T, IntoCodepageError>;
+
+    /// Do the conversion, replacing unrepresentable characters according to the specified policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, ReplacementPolicy, IntoCp437};
+    /// let unicode = "Jurek je żurek w żupanie.".to_string();
+    ///
+    /// let result = unicode.into_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(b'?'));
+    /// assert_eq!(result.encoded, b"Jurek je ?urek w ?upanie.");
+    /// assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    fn into_cp437_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T>;
 }
 
-impl IntoCp437<Vec<u8>> for String {
-    fn into_cp437(self, dialect: &Cp437Dialect) -> Result<Vec<u8>, IntoCp437Error> {
-        if self.chars().all(|c| dialect.overlap_unicode(c)) {
-            Ok(self.into_bytes())
-        } else {
-            to_cp437_impl_meat(&self, dialect).map_err(|e| {
-                IntoCp437Error {
-                    string: self,
-                    error: e,
-                }
-            })
-        }
+#[cfg(feature = "alloc")]
+impl<T, S: IntoCodepage<T>> IntoCp437<T> for S {
+    fn into_cp437(self, dialect: &SingleByteCodepage) -> Result<T, IntoCodepageError> {
+        self.into_codepage(dialect)
+    }
+
+    fn into_cp437_lossy(self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T> {
+        self.into_codepage_lossy(dialect, policy)
+    }
+}
+
+
+/// Borrow (if possible) Unicode data as data in a single-byte codepage.
+///
+/// # Examples
+///
+/// Good:
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, ToCodepage};
+/// let cp850 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x3A, 0x20,
+///              0x9D, 0x72, 0x65, 0x73, 0x75, 0x6E, 0x64, 0x20, 0x9B, 0x6C, 0x20, 0x9C, 0x35];
+/// let unicode = "Local news: Øresund øl £5";
+///
+/// assert_eq!(unicode.to_codepage(&CP850_CONTROL), Ok(cp850[..].into()));
+/// ```
+///
+/// Unrepresentable:
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, ToCodepage};
+/// // Ż cannot be represented in cp850
+/// let unicode = "Jurek je żurek w żupanie.";
+///
+/// let error = unicode.to_codepage(&CP850_CONTROL).unwrap_err();
+/// assert_eq!(error.representable_up_to, 9);
+/// ```
+#[cfg(feature = "alloc")]
+pub trait ToCodepage<'s, T> {
+    /// Do the conversion.
+    fn to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<T, CodepageError>;
+
+    /// Do the conversion, replacing unrepresentable characters according to the specified policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP850_CONTROL, ReplacementPolicy, ToCodepage};
+    /// let unicode = "Jurek je żurek w żupanie.";
+    ///
+    /// let result = unicode.to_codepage_lossy(&CP850_CONTROL, ReplacementPolicy::Skip);
+    /// assert_eq!(result.encoded, &b"Jurek je urek w upanie."[..]);
+    /// assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    fn to_codepage_lossy(&'s self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T>;
+}
+
+#[cfg(feature = "alloc")]
+impl<'s> ToCodepage<'s, Cow<'s, [u8]>> for str {
+    fn to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        to_codepage_cow_impl(self, dialect)
+    }
+
+    fn to_codepage_lossy(&'s self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Cow<'s, [u8]>> {
+        to_codepage_lossy_cow_impl(self, dialect, policy)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, S: AsRef<str>> ToCodepage<'s, Cow<'s, [u8]>> for S {
+    fn to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        to_codepage_cow_impl(self.as_ref(), dialect)
+    }
+
+    fn to_codepage_lossy(&'s self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Cow<'s, [u8]>> {
+        to_codepage_lossy_cow_impl(self.as_ref(), dialect, policy)
     }
 }
 
 
 /// Borrow (if possible) Unicode data as cp437 data.
 ///
+/// This is [`ToCodepage`](trait.ToCodepage.html) under its original name, and is implemented for everything that implements it.
+///
 /// # Examples
 ///
 /// Good:
@@ -209,42 +640,123 @@ impl IntoCp437<Vec<u8>> for String {
 /// let error = unicode.to_cp437(&CP437_CONTROL).unwrap_err();
 /// assert_eq!(error.representable_up_to, 9);
 /// ```
+#[cfg(feature = "alloc")]
 pub trait ToCp437<'s, T> {
     /// Do the conversion.
-    fn to_cp437(&'s self, dialect: &Cp437Dialect) -> Result<T, Cp437Error>;
+    fn to_cp437(&'s self, dialect: &SingleByteCodepage) -> Result<T, CodepageError>;
+
+    /// Do the conversion, replacing unrepresentable characters according to the specified policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::{CP437_CONTROL, ReplacementPolicy, ToCp437};
+    /// let unicode = "Jurek je żurek w żupanie.";
+    ///
+    /// let result = unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Skip);
+    /// assert_eq!(result.encoded, &b"Jurek je urek w upanie."[..]);
+    /// assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+    /// ```
+    fn to_cp437_lossy(&'s self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T>;
 }
 
-impl<'s> ToCp437<'s, Cow<'s, [u8]>> for str {
-    fn to_cp437(&'s self, dialect: &Cp437Dialect) -> Result<Cow<'s, [u8]>, Cp437Error> {
-        to_cp437_cow_impl(&self, dialect)
+#[cfg(feature = "alloc")]
+impl<'s, T, S: ToCodepage<'s, T> + ?Sized> ToCp437<'s, T> for S {
+    fn to_cp437(&'s self, dialect: &SingleByteCodepage) -> Result<T, CodepageError> {
+        self.to_codepage(dialect)
     }
-}
 
-impl<'s, S: AsRef<str>> ToCp437<'s, Cow<'s, [u8]>> for S {
-    fn to_cp437(&'s self, dialect: &Cp437Dialect) -> Result<Cow<'s, [u8]>, Cp437Error> {
-        to_cp437_cow_impl(self.as_ref(), dialect)
+    fn to_cp437_lossy(&'s self, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<T> {
+        self.to_codepage_lossy(dialect, policy)
     }
 }
 
 
-fn to_cp437_cow_impl<'c>(whom: &'c str, dialect: &Cp437Dialect) -> Result<Cow<'c, [u8]>, Cp437Error> {
-    if whom.chars().all(|c| dialect.overlap_unicode(c)) {
+#[cfg(feature = "alloc")]
+fn to_codepage_cow_impl<'c>(whom: &'c str, dialect: &SingleByteCodepage) -> Result<Cow<'c, [u8]>, CodepageError> {
+    let overlapping = dialect.overlap_unicode_prefix(whom);
+    if overlapping == whom.len() {
         Ok(Cow::Borrowed(whom.as_bytes()))
     } else {
-        to_cp437_impl_meat(whom, dialect).map(Cow::Owned)
+        to_codepage_impl_meat(whom, overlapping, dialect).map(Cow::Owned)
     }
 }
 
-fn to_cp437_impl_meat(whom: &str, dialect: &Cp437Dialect) -> Result<Vec<u8>, Cp437Error> {
-    let mut result = Vec::with_capacity(whom.chars().count());
+/// Encode the specified string, the first `overlapping` bytes of which are known to overlap, and so are copied verbatim.
+#[cfg(feature = "alloc")]
+fn to_codepage_impl_meat(whom: &str, overlapping: usize, dialect: &SingleByteCodepage) -> Result<Vec<u8>, CodepageError> {
+    let mut result = Vec::with_capacity(whom.len());
+    result.extend_from_slice(&whom.as_bytes()[..overlapping]);
 
-    for c in whom.chars() {
+    for (i, c) in whom[overlapping..].char_indices().map(|(i, c)| (overlapping + i, c)) {
         if let Some(b) = dialect.encode(c) {
             result.push(b);
         } else {
-            return Err(Cp437Error { representable_up_to: result.len() });
+            // A byte per character so far
+            return Err(CodepageError {
+                representable_up_to: i,
+                char_index: result.len(),
+                unrepresentable: c,
+            });
         }
     }
 
     Ok(result)
 }
+
+/// Attach the specified string and its other unrepresentable characters to the specified error therein.
+#[cfg(feature = "alloc")]
+fn into_codepage_error(string: String, error: CodepageError, dialect: &SingleByteCodepage) -> IntoCodepageError {
+    let unrepresentable = dialect.unrepresentable(&string[error.representable_up_to..]).map(|(i, c)| (error.representable_up_to + i, c)).collect();
+    IntoCodepageError {
+        string,
+        error,
+        unrepresentable,
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn to_codepage_lossy_cow_impl<'c>(whom: &'c str, dialect: &SingleByteCodepage, policy: ReplacementPolicy) -> LossyEncoded<Cow<'c, [u8]>> {
+    let overlapping = dialect.overlap_unicode_prefix(whom);
+    if overlapping == whom.len() {
+        LossyEncoded {
+            encoded: Cow::Borrowed(whom.as_bytes()),
+            replaced: vec![],
+        }
+    } else {
+        let LossyEncoded { encoded, replaced } = to_codepage_lossy_impl_meat(whom, overlapping, dialect, policy);
+        LossyEncoded {
+            encoded: Cow::Owned(encoded),
+            replaced,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn to_codepage_lossy_impl_meat(whom: &str, overlapping: usize, dialect: &SingleByteCodepage, mut policy: ReplacementPolicy) -> LossyEncoded<Vec<u8>> {
+    let mut result = Vec::with_capacity(whom.len());
+    result.extend_from_slice(&whom.as_bytes()[..overlapping]);
+    let mut replaced = vec![];
+
+    for (i, c) in whom[overlapping..].char_indices().map(|(i, c)| (overlapping + i, c)) {
+        if let Some(b) = dialect.encode(c) {
+            result.push(b);
+        } else {
+            match policy {
+                ReplacementPolicy::Substitute(b) => result.push(b),
+                ReplacementPolicy::Skip => {}
+                ReplacementPolicy::With(ref mut f) => {
+                    if let Some(bs) = f(c) {
+                        result.extend(bs);
+                    }
+                }
+            }
+            replaced.push((i, c));
+        }
+    }
+
+    LossyEncoded {
+        encoded: result,
+        replaced,
+    }
+}
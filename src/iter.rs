@@ -0,0 +1,183 @@
+use self::super::{SingleByteCodepage, CodepageError};
+use std::ops::RangeInclusive;
+
+
+/// Iterator decoding codepoints in a single-byte codepage into Unicode ones, lazily.
+///
+/// Returned by [`SingleByteCodepage::decode_iter()`](struct.SingleByteCodepage.html#method.decode_iter)
+/// and [`DecodeCodepage::decode_codepage()`](trait.DecodeCodepage.html#tymethod.decode_codepage).
+#[derive(Debug, Clone)]
+pub struct DecodeIter<'d, I> {
+    dialect: &'d SingleByteCodepage,
+    iter: I,
+}
+
+impl<'d, I: Iterator<Item = u8>> DecodeIter<'d, I> {
+    pub(crate) fn new(dialect: &'d SingleByteCodepage, iter: I) -> DecodeIter<'d, I> {
+        DecodeIter { dialect, iter }
+    }
+}
+
+impl<'d, I: Iterator<Item = u8>> Iterator for DecodeIter<'d, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.iter.next().map(|b| self.dialect.decode(b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'d, I: DoubleEndedIterator<Item = u8>> DoubleEndedIterator for DecodeIter<'d, I> {
+    fn next_back(&mut self) -> Option<char> {
+        self.iter.next_back().map(|b| self.dialect.decode(b))
+    }
+}
+
+impl<'d, I: ExactSizeIterator<Item = u8>> ExactSizeIterator for DecodeIter<'d, I> {}
+
+
+/// Iterator encoding Unicode codepoints as ones in a single-byte codepage, lazily.
+///
+/// Each unrepresentable character yields an error, whose indices are counted from the start of the iteration,
+/// as if the characters were collected into a string; iteration can go on past it.
+///
+/// Returned by [`SingleByteCodepage::encode_iter()`](struct.SingleByteCodepage.html#method.encode_iter)
+/// and [`EncodeCodepage::encode_codepage()`](trait.EncodeCodepage.html#tymethod.encode_codepage).
+#[derive(Debug, Clone)]
+pub struct EncodeIter<'d, I> {
+    dialect: &'d SingleByteCodepage,
+    iter: I,
+    byte_index: usize,
+    char_index: usize,
+}
+
+impl<'d, I: Iterator<Item = char>> EncodeIter<'d, I> {
+    pub(crate) fn new(dialect: &'d SingleByteCodepage, iter: I) -> EncodeIter<'d, I> {
+        EncodeIter {
+            dialect,
+            iter,
+            byte_index: 0,
+            char_index: 0,
+        }
+    }
+}
+
+impl<'d, I: Iterator<Item = char>> Iterator for EncodeIter<'d, I> {
+    type Item = Result<u8, CodepageError>;
+
+    fn next(&mut self) -> Option<Result<u8, CodepageError>> {
+        let c = self.iter.next()?;
+        let ret = self.dialect.encode(c).ok_or(CodepageError {
+            representable_up_to: self.byte_index,
+            char_index: self.char_index,
+            unrepresentable: c,
+        });
+
+        self.byte_index += c.len_utf8();
+        self.char_index += 1;
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'d, I: ExactSizeIterator<Item = char>> ExactSizeIterator for EncodeIter<'d, I> {}
+
+
+/// Decode an iterator over codepoints in a single-byte codepage, lazily.
+///
+/// This is implemented for all iterators over bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, DecodeCodepage};
+/// let cp437 = [0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x0A,
+///              0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E];
+///
+/// let headline: String = cp437.iter().cloned().take_while(|&b| b != 0x0A).decode_codepage(&CP437_CONTROL).collect();
+/// assert_eq!(headline, "Local news");
+///
+/// let price = cp437.split(|&b| b == 0x0A).nth(1).unwrap().iter().cloned().decode_codepage(&CP437_CONTROL).nth(1);
+/// assert_eq!(price, Some('½'));
+/// ```
+pub trait DecodeCodepage: Iterator<Item = u8> + Sized {
+    /// Do the conversion.
+    fn decode_codepage<'d>(self, dialect: &'d SingleByteCodepage) -> DecodeIter<'d, Self>;
+}
+
+impl<I: Iterator<Item = u8>> DecodeCodepage for I {
+    fn decode_codepage<'d>(self, dialect: &'d SingleByteCodepage) -> DecodeIter<'d, Self> {
+        DecodeIter::new(dialect, self)
+    }
+}
+
+
+/// Encode an iterator over Unicode codepoints as ones in a single-byte codepage, lazily.
+///
+/// This is implemented for all iterators over `char`s.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, EncodeCodepage};
+/// let cp437: Result<Vec<_>, _> = "Local news".chars().rev().encode_codepage(&CP437_CONTROL).collect();
+/// assert_eq!(cp437, Ok(b"swen lacoL".to_vec()));
+///
+/// let cp437: Vec<_> = "Eżektor".chars().encode_codepage(&CP437_CONTROL).filter_map(Result::ok).collect();
+/// assert_eq!(cp437, b"Eektor");
+/// ```
+pub trait EncodeCodepage: Iterator<Item = char> + Sized {
+    /// Do the conversion.
+    fn encode_codepage<'d>(self, dialect: &'d SingleByteCodepage) -> EncodeIter<'d, Self>;
+}
+
+impl<I: Iterator<Item = char>> EncodeCodepage for I {
+    fn encode_codepage<'d>(self, dialect: &'d SingleByteCodepage) -> EncodeIter<'d, Self> {
+        EncodeIter::new(dialect, self)
+    }
+}
+
+
+/// Iterator over all codepoints in a single-byte codepage, alongside the Unicode ones they decode to.
+///
+/// Returned by [`SingleByteCodepage::mappings()`](struct.SingleByteCodepage.html#method.mappings).
+#[derive(Debug, Clone)]
+pub struct Mappings<'d> {
+    dialect: &'d SingleByteCodepage,
+    cp437: RangeInclusive<u8>,
+}
+
+impl<'d> Mappings<'d> {
+    pub(crate) fn new(dialect: &'d SingleByteCodepage) -> Mappings<'d> {
+        Mappings {
+            dialect,
+            cp437: 0..=0xFF,
+        }
+    }
+}
+
+impl<'d> Iterator for Mappings<'d> {
+    type Item = (u8, char);
+
+    fn next(&mut self) -> Option<(u8, char)> {
+        self.cp437.next().map(|b| (b, self.dialect.decode(b)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cp437.size_hint()
+    }
+}
+
+impl<'d> DoubleEndedIterator for Mappings<'d> {
+    fn next_back(&mut self) -> Option<(u8, char)> {
+        self.cp437.next_back().map(|b| (b, self.dialect.decode(b)))
+    }
+}
+
+impl<'d> ExactSizeIterator for Mappings<'d> {}
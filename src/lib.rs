@@ -1,16 +1,64 @@
 //! Conversion to and from codepage 437.
 //!
+//! The other DOS code pages, cp737, cp850, cp852, cp857, cp860, cp861, cp862, cp863, cp865, and cp866,
+//! are available through the same API, with the `CP850_CONTROL`, `CP850_WINGDINGS`, &c. dialects.
+//! So are the Windows code pages, cp1250 through cp1258, and ISO-8859-1 through ISO-8859-16,
+//! with the `CP1252`, `ISO_8859_1`, &c. dialects; codepoints undefined therein aren't encoded to,
+//! and can be rejected when decoding with the `try_` conversions, or replaced with U+FFFD with the `_lossy` ones.
+//! And the EBCDIC code pages, cp037, cp500, and cp1047, with the `CP037`, `CP500`, and `CP1047` dialects,
+//! which share nothing with ASCII, so conversions thereto and therefrom never borrow.
+//!
+//! The double-byte East Asian code pages, cp932, cp936, cp949, and cp950, are `DoubleByteCodepage`s, like `CP932`,
+//! converted with the `{Borrow,}FromDbcs` and `ToDbcs` traits instead.
+//! The 8-bit home computers' PETSCII and ATASCII, whose bytes decode differently depending on the ones before them,
+//! are `StatefulCodepage`s, `PETSCII` and `ATASCII`, converted with their `decoder()` and `encoder()`;
+//! each of their modes is a dialect, like `PETSCII_SHIFTED`.
+//!
+//! All the others are `SingleByteCodepage`s (formerly `Cp437Dialect`, which remains as an alias),
+//! and the codepage-neutral `{Borrow,}FromCodepage` and `{Into,To}Codepage` traits work with any of them;
+//! the cp437-named traits below are the same thing under their original names.
+//! Codepages not built in can be loaded from mapping files at runtime with `SingleByteCodepage::from_mapping_reader()`,
+//! or put together with `SingleByteCodepageBuilder`.
+//! Where binary data needs to be re-encoded byte-exactly, decode it with the dialect's `round_trip()`.
+//! A dialect's `mappings()`, `aliases()`, `remaps()`, and `name()`s describe what it maps,
+//! and its `diff()` with another, what they map differently.
+//!
 //! Use the `{Borrow,}FromCp437` traits to convert series of cp437 bytes to Unicode,
-//! and the `cp437_to_unicode()` function to decode a single codepoint.
+//! and the `cp437_to_unicode()` function to decode a single codepoint
+//! (or a dialect's `try_decode()`, to tell undefined codepoints apart).
 //!
 //! Use the `{Into,To}Cp437` traits to convert Unicode to a series of cp437 bytes,
 //! and the `unicode_to_cp437()` function to encode a single codepoint.
 //!
+//! Use the `Transliterate` trait to convert Unicode to cp437, replacing unrepresentable characters with look-alikes,
+//! and the `transliteration()` function to get the replacement for a single codepoint.
+//!
+//! Use the `DecodeCodepage` and `EncodeCodepage` traits, or the dialects' `decode_iter()` and `encode_iter()`,
+//! to convert iterators over cp437 bytes and Unicode `char`s lazily.
+//!
+//! Use `Cp437Reader` to decode a stream of cp437 bytes to UTF-8 as it's being read,
+//! and `Cp437Writer` to encode UTF-8 written to a stream as cp437;
+//! both work with `DoubleByteCodepage`s and `StatefulCodepage`s, too.
+//!
+//! # Features
+//!
+//! The default `std` feature enables the reader, writer, mapping file loading, and `std::error::Error` impls,
+//! and implies `alloc`, which enables the conversion traits, transliteration, remapping, and the builder.
+//! Without either, the crate is `no_std`, and doesn't allocate:
+//! single codepoints can still be converted with the dialects' `decode()` and `encode()`,
+//! and series thereof into caller-provided buffers with their `decode_to_slice()` and `encode_to_slice()`,
+//! or piecewise, with `decode_to_utf8()` and `encode_from_utf8()`.
+//! With `alloc`, `decode_into()` and `encode_into()` append to a `String` or `Vec<u8>`, which can be reused across conversions.
+//!
+//! The `bytes` feature, which implies `alloc`, implements `FromCp437` for `bytes::Bytes`;
+//! likewise, `smartstring` and `compact_str` implement `IntoCp437` for `smartstring::SmartString` and `compact_str::CompactString`.
+//!
 //! # Examples
 //!
 //! Borrowing from a buffer:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use codepage_437::{CP437_CONTROL, BorrowFromCp437};
 //! # use std::borrow::Cow;
 //! # /*
@@ -26,11 +74,13 @@
 //! // Also valid:
 //! let in_unicode = String::borrow_from_cp437(data, &CP437_CONTROL);
 //! # assert_eq!(in_unicode, "Local news");
+//! # }
 //! ```
 //!
 //! Moving out of a buffer:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use codepage_437::{CP437_CONTROL, FromCp437};
 //! # /*
 //! let data = vec![/* buffer moved in from somewhere */];
@@ -41,11 +91,13 @@
 //! ///      if it only contains overlapping characters
 //! let in_unicode = String::from_cp437(data, &CP437_CONTROL);
 //! # assert_eq!(in_unicode, "Local news");
+//! # }
 //! ```
 //!
 //! Borrowing from a `&str`:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use codepage_437::{CP437_CONTROL, ToCp437};
 //! let data = "Some string.";
 //!
@@ -59,11 +111,13 @@
 //! let data = "Some string.".to_string();
 //! let in_cp437 = data.to_cp437(&CP437_CONTROL);
 //! # assert_eq!(in_cp437, Ok([0x53, 0x6F, 0x6D, 0x65, 0x20, 0x73, 0x74, 0x72, 0x69, 0x6E, 0x67, 0x2E][..].into()));
+//! # }
 //! ```
 //!
 //! Moving out of a `String`:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use codepage_437::{CP437_CONTROL, IntoCp437};
 //! let data = "Some string.".to_string();
 //!
@@ -71,11 +125,13 @@
 //! ///      if it only contains overlapping characters
 //! let in_cp437 = data.into_cp437(&CP437_CONTROL);
 //! # assert_eq!(in_cp437, Ok([0x53, 0x6F, 0x6D, 0x65, 0x20, 0x73, 0x74, 0x72, 0x69, 0x6E, 0x67, 0x2E][..].into()));
+//! # }
 //! ```
 //!
 //! Unrepresentable Unicode:
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! # use codepage_437::{CP437_CONTROL, ToCp437};
 //! // Ż has no representation in cp437
 //! let data = "Jurek żelaznym żurkiem żre żupan.";
@@ -85,13 +141,69 @@
 //! // result.unwrap_err() is Cp437Error (or IntoCp437Error for into_cp437()),
 //! //   with an API modeled after libstd's {From,}Utf8Error
 //! # assert_eq!(result.unwrap_err().representable_up_to, 6);
+//! # }
 //! ```
 
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate alloc;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "smartstring")]
+extern crate smartstring;
+#[cfg(feature = "compact_str")]
+extern crate compact_str;
+
+
+#[cfg(feature = "alloc")]
 mod decode;
 mod encode;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod writer;
+mod transliterate;
 mod dialect;
+mod iter;
+mod dbcs;
+mod stateful;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "std")]
+mod mapping;
 
 pub use self::dialect::*;
-pub use self::decode::{BorrowFromCp437, FromCp437};
-pub use self::encode::{IntoCp437Error, Cp437Error, IntoCp437, ToCp437};
+pub use self::transliterate::transliteration;
+pub use self::encode::{CodepageError, Cp437Error, Unrepresentable};
+pub use self::dbcs::*;
+pub use self::stateful::*;
+pub use self::iter::{DecodeIter, EncodeIter, DecodeCodepage, EncodeCodepage, Mappings};
+#[cfg(feature = "alloc")]
+pub use self::diff::CodepageDiff;
+#[cfg(feature = "alloc")]
+pub use self::builder::{SingleByteCodepageBuilder, Cp437DialectBuilder, BuildError};
+#[cfg(feature = "alloc")]
+pub use self::transliterate::Transliterate;
+#[cfg(feature = "alloc")]
+pub use self::decode::{DecodeError, BorrowFromCodepage, BorrowFromCp437, FromCodepage, FromCp437};
+#[cfg(feature = "alloc")]
+pub use self::encode::{ReplacementPolicy, IntoCodepageError, IntoCp437Error, LossyEncoded, LossyCp437, IntoCodepage, IntoCp437, ToCodepage, ToCp437};
+#[cfg(feature = "std")]
+pub use self::mapping::MappingError;
+#[cfg(feature = "std")]
+pub use self::stream::StreamCodepage;
+#[cfg(feature = "std")]
+pub use self::reader::Cp437Reader;
+#[cfg(feature = "std")]
+pub use self::writer::Cp437Writer;
@@ -0,0 +1,133 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::error::Error;
+use std::{char, fmt};
+
+
+/// Errors which can occur when parsing a mapping file.
+///
+/// Line numbers start at `1`.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{SingleByteCodepage, MappingError};
+/// let mapping = "0x41\t0x0041\tLATIN CAPITAL LETTER A\n\
+///                0x41\t0x0391\tGREEK CAPITAL LETTER ALPHA\n";
+///
+/// match SingleByteCodepage::from_mapping_reader(mapping.as_bytes()) {
+///     Err(MappingError::Duplicate { line: 2, first: 1 }) => {}
+///     other => panic!("{:?}", other),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum MappingError {
+    /// Reading the mapping failed, or it wasn't UTF-8.
+    Io(io::Error),
+    /// The line isn't a mapping entry: it's missing the Unicode column, or a code isn't a hexadecimal number.
+    Malformed { line: usize },
+    /// A code on the line doesn't start with `0x`.
+    BadPrefix { line: usize },
+    /// The codepage code doesn't fit in a byte, or the Unicode one isn't a valid scalar value.
+    OutOfRange { line: usize },
+    /// The line maps a codepage code already mapped on line `first`.
+    Duplicate { line: usize, first: usize },
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MappingError::Io(ref e) => write!(f, "failed to read mapping: {}", e),
+            MappingError::Malformed { line } => write!(f, "line {}: malformed mapping entry", line),
+            MappingError::BadPrefix { line } => write!(f, "line {}: code not prefixed with \"0x\"", line),
+            MappingError::OutOfRange { line } => write!(f, "line {}: code out of range", line),
+            MappingError::Duplicate { line, first } => write!(f, "line {}: code already mapped on line {}", line, first),
+        }
+    }
+}
+
+impl Error for MappingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            MappingError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MappingError {
+    fn from(e: io::Error) -> MappingError {
+        MappingError::Io(e)
+    }
+}
+
+
+/// Parse a mapping file into its `(codepage, Unicode)` entries, in order of appearance.
+///
+/// Both the Unicode Consortium format (whitespace-separated codes, followed by a `#` comment)
+/// and the `dialect-specs` one (tab-separated, with a header line and a bare comment column) are accepted.
+/// Codes mapped to nothing, i.e. followed only by a `#UNDEFINED` comment, or with an empty Unicode column, are `None`.
+pub fn parse_mapping<R: Read>(reader: R) -> Result<Vec<(u8, Option<char>)>, MappingError> {
+    let mut entries = Vec::new();
+    let mut defined_on = [None; 256];
+    let mut seen_entry = false;
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line_no = i + 1;
+        let line = line?;
+
+        let entry = line.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+
+        let (code, unicode) = if entry.contains('\t') {
+            let mut fields = entry.split('\t').map(str::trim);
+            (fields.next().unwrap(), fields.next())
+        } else {
+            let mut fields = entry.split_whitespace();
+            (fields.next().unwrap(), fields.next())
+        };
+
+        let first_entry = !seen_entry;
+        seen_entry = true;
+        if first_entry && !code.starts_with("0x") && !unicode.map(|u| u.starts_with("0x")).unwrap_or(false) {
+            // Column header
+            continue;
+        }
+
+        let code = parse_code(code, line_no)?;
+        if code > 0xFF {
+            return Err(MappingError::OutOfRange { line: line_no });
+        }
+        let code = code as u8;
+
+        let unicode = match unicode {
+            Some(u) if u.is_empty() || u.starts_with('#') => None,
+            Some(u) => Some(char::from_u32(parse_code(u, line_no)?).ok_or(MappingError::OutOfRange { line: line_no })?),
+            None => return Err(MappingError::Malformed { line: line_no }),
+        };
+
+        if let Some(first) = defined_on[code as usize] {
+            return Err(MappingError::Duplicate {
+                line: line_no,
+                first,
+            });
+        }
+        defined_on[code as usize] = Some(line_no);
+
+        entries.push((code, unicode));
+    }
+
+    Ok(entries)
+}
+
+fn parse_code(code: &str, line: usize) -> Result<u32, MappingError> {
+    if !code.starts_with("0x") {
+        return Err(MappingError::BadPrefix { line });
+    }
+    let code = &code[2..];
+    if code.is_empty() || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(MappingError::Malformed { line });
+    }
+    u32::from_str_radix(code, 16).map_err(|_| MappingError::OutOfRange { line })
+}
@@ -0,0 +1,148 @@
+use self::super::{SingleByteCodepage, StreamCodepage};
+use std::io::{self, BufRead, Read};
+
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+
+/// Adapter decoding a stream of cp437 bytes into a stream of UTF-8 bytes.
+///
+/// Data is read from the underlying reader in chunks, decoded, and buffered,
+/// so a cp437 codepoint expanding to multiple UTF-8 bytes can be safely split across multiple `read()` calls.
+///
+/// Since the decoded data is buffered anyway, this also implements `BufRead`.
+///
+/// Streams in [`DoubleByteCodepage`](struct.DoubleByteCodepage.html)s can be decoded too:
+/// a lead byte at the end of a chunk is kept until its trail byte is read,
+/// and one at the end of the stream decodes to U+FFFD REPLACEMENT CHARACTER.
+/// So can ones in [`StatefulCodepage`](struct.StatefulCodepage.html)s, the state being carried across chunks.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, Cp437Reader};
+/// # use std::io::{BufRead, Read};
+/// let cp437 = &[0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x0A,
+///               0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E][..];
+///
+/// let mut unicode = String::new();
+/// Cp437Reader::new(cp437, &CP437_CONTROL).read_to_string(&mut unicode).unwrap();
+/// assert_eq!(unicode, "Local news\n₧½ million");
+///
+/// let lines: Vec<_> = Cp437Reader::new(cp437, &CP437_CONTROL).lines().map(Result::unwrap).collect();
+/// assert_eq!(lines, ["Local news", "₧½ million"]);
+/// ```
+///
+/// A lead byte split from its trail byte:
+///
+/// ```
+/// # use codepage_437::{CP932, Cp437Reader};
+/// # use std::io::Read;
+/// let cp932 = &[0x93, 0xFA, 0x96, 0x7B, 0x8C, 0xEA][..];
+///
+/// let mut unicode = String::new();
+/// Cp437Reader::with_capacity(3, cp932, &CP932).read_to_string(&mut unicode).unwrap();
+/// assert_eq!(unicode, "日本語");
+/// ```
+#[derive(Debug)]
+pub struct Cp437Reader<'d, R: Read, D: StreamCodepage + ?Sized + 'd = SingleByteCodepage> {
+    inner: R,
+    dialect: &'d D,
+    state: D::State,
+
+    /// Followed by a spare byte, for an incomplete codepoint carried over from the previous chunk
+    cp437: Box<[u8]>,
+    cp437_carried: usize,
+    unicode: String,
+    unicode_pos: usize,
+}
+
+impl<'d, R: Read, D: StreamCodepage + ?Sized> Cp437Reader<'d, R, D> {
+    /// Create a new decoding adapter around the specified reader.
+    pub fn new(inner: R, dialect: &'d D) -> Cp437Reader<'d, R, D> {
+        Cp437Reader::with_capacity(DEFAULT_BUF_SIZE, inner, dialect)
+    }
+
+    /// Create a new decoding adapter around the specified reader, reading up to `capacity` cp437 bytes at a time.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is `0`.
+    pub fn with_capacity(capacity: usize, inner: R, dialect: &'d D) -> Cp437Reader<'d, R, D> {
+        assert!(capacity != 0, "Cp437Reader capacity must be non-zero");
+
+        Cp437Reader {
+            inner,
+            dialect,
+            state: D::State::default(),
+
+            cp437: vec![0; capacity + 1].into_boxed_slice(),
+            cp437_carried: 0,
+            unicode: String::with_capacity(capacity),
+            unicode_pos: 0,
+        }
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from it will skip over the data that was read into, but not yet out of, this adapter.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying reader.
+    ///
+    /// Decoded data that wasn't yet read out is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<'d, R: Read, D: StreamCodepage + ?Sized> Read for Cp437Reader<'d, R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = {
+            let decoded = self.fill_buf()?;
+            let read = decoded.len().min(buf.len());
+            buf[..read].copy_from_slice(&decoded[..read]);
+            read
+        };
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl<'d, R: Read, D: StreamCodepage + ?Sized> BufRead for Cp437Reader<'d, R, D> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.unicode_pos >= self.unicode.len() {
+            self.unicode.clear();
+            self.unicode_pos = 0;
+
+            // A chunk consisting only of an incomplete codepoint decodes to nothing, but isn't the end of the stream
+            loop {
+                let capacity = self.cp437.len() - 1;
+                let read = self.inner.read(&mut self.cp437[self.cp437_carried..capacity + self.cp437_carried])?;
+
+                let data_len = self.cp437_carried + read;
+                let decoded = self.dialect.decode_chunk(&mut self.state, &self.cp437[..data_len], read == 0, &mut self.unicode);
+                self.cp437.copy_within(decoded..data_len, 0);
+                self.cp437_carried = data_len - decoded;
+
+                if read == 0 || !self.unicode.is_empty() {
+                    break;
+                }
+            }
+        }
+
+        Ok(&self.unicode.as_bytes()[self.unicode_pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.unicode_pos = (self.unicode_pos + amt).min(self.unicode.len());
+    }
+}
+
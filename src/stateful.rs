@@ -0,0 +1,403 @@
+use self::super::{SingleByteCodepage, PETSCII_UNSHIFTED, PETSCII_SHIFTED, ATASCII_NORMAL};
+#[cfg(feature = "alloc")]
+use self::super::CodepageError;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+
+/// A byte changing the state of a [`StatefulCodepage`](struct.StatefulCodepage.html).
+///
+/// If it's undefined in the mode it's read in, it doesn't decode to anything, and only changes the state;
+/// otherwise, it also decodes to a character, like a carriage return turning off reverse video.
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct StateSwitch {
+    /// The byte.
+    pub code: u8,
+    /// The index of the mode it switches to, if any.
+    pub mode: Option<usize>,
+    /// Whether it turns reverse video on or off, if either.
+    pub reverse: Option<bool>,
+}
+
+/// The state of a stream in a [`StatefulCodepage`](struct.StatefulCodepage.html).
+///
+/// Streams start in the first mode, in normal video.
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd, Default)]
+pub struct CodepageState {
+    /// The index of the current mode.
+    pub mode: usize,
+    /// Whether the current character is, or the following ones will be, in reverse video.
+    pub reverse: bool,
+}
+
+impl CodepageState {
+    /// Apply the switches for the specified byte, returning whether there were any.
+    fn switch(&mut self, switches: &[StateSwitch], code: u8) -> bool {
+        let mut switched = false;
+        for switch in switches.iter().filter(|s| s.code == code) {
+            if let Some(mode) = switch.mode {
+                self.mode = mode;
+            }
+            if let Some(reverse) = switch.reverse {
+                self.reverse = reverse;
+            }
+            switched = true;
+        }
+        switched
+    }
+}
+
+
+/// Specifier for a codepage whose bytes decode differently depending on what came before,
+/// like the 8-bit home computers' ones.
+///
+/// Each mode is a [`SingleByteCodepage`](struct.SingleByteCodepage.html), and control bytes switch between them,
+/// and turn reverse video on and off; in some codepages, the high bit of a byte undefined in the current mode
+/// selects the reverse video counterpart of the rest of it instead.
+///
+/// Decoding and encoding is done with a [`StatefulDecoder`](struct.StatefulDecoder.html)
+/// or [`StatefulEncoder`](struct.StatefulEncoder.html), which carry the state across calls,
+/// or with [`Cp437Reader`](struct.Cp437Reader.html) and [`Cp437Writer`](struct.Cp437Writer.html).
+/// Reverse video has no Unicode representation, so converting to and from a `String` drops it;
+/// it can be tracked per character with the decoder's [`state()`](struct.StatefulDecoder.html#method.state),
+/// and requested per character from the encoder.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use codepage_437::PETSCII;
+/// let petscii = [0x0E, 0xD2, 0x45, 0x41, 0x44, 0x59, 0x2E, 0x0D, 0x8E, 0xC1, 0x12, 0xD3, 0x92, 0xD3];
+///
+/// let mut unicode = String::new();
+/// PETSCII.decode_into(&petscii, &mut unicode);
+/// assert_eq!(unicode, "Ready.\r♠♥♥");
+///
+/// let mut decoder = PETSCII.decoder();
+/// let decoded: Vec<_> = petscii.iter().filter_map(|&b| decoder.decode(b).map(|c| (c, decoder.state().reverse))).collect();
+/// assert_eq!(decoded[8..], [('♥', true), ('♥', false)]);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct StatefulCodepage {
+    modes: &'static [&'static SingleByteCodepage],
+    switches: &'static [StateSwitch],
+    high_bit_reverse: bool,
+}
+
+impl StatefulCodepage {
+    /// Create a codepage out of the specified modes and switches between them,
+    /// with the high bit selecting reverse video, if `high_bit_reverse`.
+    ///
+    /// # Panics
+    ///
+    /// If there are no modes, or a switch is to one that doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// # use codepage_437::{StatefulCodepage, StateSwitch, CP437_CONTROL, CP850_CONTROL};
+    /// static CP437_850: StatefulCodepage =
+    ///     StatefulCodepage::new(&[&CP437_CONTROL, &CP850_CONTROL],
+    ///                           &[StateSwitch { code: 0x0E, mode: Some(1), reverse: None },
+    ///                             StateSwitch { code: 0x0F, mode: Some(0), reverse: None }],
+    ///                           false);
+    ///
+    /// let mut cp437_850 = vec![];
+    /// CP437_850.encode_into("£¥Á", &mut cp437_850).unwrap();
+    /// assert_eq!(cp437_850, &[0x9C, 0x9D, 0x0E, 0xB5]);
+    /// # }
+    /// ```
+    pub const fn new(modes: &'static [&'static SingleByteCodepage], switches: &'static [StateSwitch], high_bit_reverse: bool) -> StatefulCodepage {
+        assert!(!modes.is_empty(), "StatefulCodepage needs at least one mode");
+
+        let mut i = 0;
+        while i < switches.len() {
+            if let Some(mode) = switches[i].mode {
+                assert!(mode < modes.len(), "StatefulCodepage switch to nonexistent mode");
+            }
+            i += 1;
+        }
+
+        StatefulCodepage {
+            modes,
+            switches,
+            high_bit_reverse,
+        }
+    }
+
+    /// Get the modes, by index.
+    pub fn modes(&self) -> &'static [&'static SingleByteCodepage] {
+        self.modes
+    }
+
+    /// Get the bytes which change the state.
+    pub fn switches(&self) -> &'static [StateSwitch] {
+        self.switches
+    }
+
+    /// Check, whether the high bit of bytes undefined in the current mode selects reverse video.
+    pub fn high_bit_reverse(&self) -> bool {
+        self.high_bit_reverse
+    }
+
+    /// Start decoding a stream in this codepage.
+    pub fn decoder<'c>(&'c self) -> StatefulDecoder<'c> {
+        StatefulDecoder {
+            codepage: self,
+            state: CodepageState::default(),
+        }
+    }
+
+    /// Start encoding a stream in this codepage.
+    #[cfg(feature = "alloc")]
+    pub fn encoder<'c>(&'c self) -> StatefulEncoder<'c> {
+        StatefulEncoder {
+            codepage: self,
+            state: CodepageState::default(),
+        }
+    }
+
+    /// Decode the specified stream in this codepage, appending it to the specified string.
+    #[cfg(feature = "alloc")]
+    pub fn decode_into(&self, data: &[u8], into: &mut String) {
+        self.decoder().decode_into(data, into)
+    }
+
+    /// Encode the specified string as a stream in this codepage, in normal video, appending it to the specified buffer.
+    ///
+    /// If that fails, the representable prefix is appended nevertheless.
+    #[cfg(feature = "alloc")]
+    pub fn encode_into(&self, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        self.encoder().encode_into(unicode, into)
+    }
+}
+
+
+/// Decoder carrying the state of a stream in a [`StatefulCodepage`](struct.StatefulCodepage.html) across bytes.
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct StatefulDecoder<'c> {
+    codepage: &'c StatefulCodepage,
+    state: CodepageState,
+}
+
+impl<'c> StatefulDecoder<'c> {
+    /// Get the current state; after decoding a character, its reverse video is the one it's displayed in.
+    pub fn state(&self) -> CodepageState {
+        self.state
+    }
+
+    /// Continue decoding from the specified state.
+    pub fn set_state(&mut self, state: CodepageState) {
+        self.state = state;
+    }
+
+    /// Decode the next byte of the stream, if it's not only a switch.
+    ///
+    /// Bytes neither a switch, nor reverse video, undefined in the current mode decode to the Unicode codepoint of the same value,
+    /// like with [`SingleByteCodepage::decode()`](struct.SingleByteCodepage.html#method.decode).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::ATASCII;
+    /// let mut decoder = ATASCII.decoder();
+    ///
+    /// assert_eq!(decoder.decode(0x41), Some('A'));
+    /// assert!(!decoder.state().reverse);
+    ///
+    /// assert_eq!(decoder.decode(0xC1), Some('A'));
+    /// assert!(decoder.state().reverse);
+    /// ```
+    pub fn decode(&mut self, code: u8) -> Option<char> {
+        let mode = self.codepage.modes[self.state.mode];
+        let switched = self.state.switch(self.codepage.switches, code);
+
+        if mode.defined(code) {
+            if self.codepage.high_bit_reverse {
+                self.state.reverse = false;
+            }
+            Some(mode.decode(code))
+        } else if switched {
+            None
+        } else if self.codepage.high_bit_reverse && code >= 0x80 && mode.defined(code & 0x7F) {
+            self.state.reverse = true;
+            Some(mode.decode(code & 0x7F))
+        } else {
+            Some(mode.decode(code))
+        }
+    }
+
+    /// Decode the specified part of the stream, appending it to the specified string.
+    #[cfg(feature = "alloc")]
+    pub fn decode_into(&mut self, data: &[u8], into: &mut String) {
+        into.reserve(data.len());
+        for &b in data {
+            if let Some(c) = self.decode(b) {
+                into.push(c);
+            }
+        }
+    }
+}
+
+
+/// Encoder carrying the state of a stream in a [`StatefulCodepage`](struct.StatefulCodepage.html) across characters.
+///
+/// Characters not in the current mode are encoded in the first one they're in, which is switched to first.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct StatefulEncoder<'c> {
+    codepage: &'c StatefulCodepage,
+    state: CodepageState,
+}
+
+#[cfg(feature = "alloc")]
+impl<'c> StatefulEncoder<'c> {
+    /// Get the current state.
+    pub fn state(&self) -> CodepageState {
+        self.state
+    }
+
+    /// Continue encoding from the specified state.
+    pub fn set_state(&mut self, state: CodepageState) {
+        self.state = state;
+    }
+
+    /// Encode the specified character in the specified video, appending it, preceded by the switches it needs, to the specified buffer.
+    ///
+    /// Characters without a reverse video counterpart are encoded in normal video.
+    ///
+    /// Returns whether the character is representable; nothing is appended, if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codepage_437::PETSCII;
+    /// let mut encoder = PETSCII.encoder();
+    /// let mut petscii = vec![];
+    ///
+    /// assert!(encoder.encode('R', false, &mut petscii));
+    /// assert!(encoder.encode('e', false, &mut petscii));
+    /// assert!(encoder.encode('♥', true, &mut petscii));
+    /// assert!(!encoder.encode('€', false, &mut petscii));
+    ///
+    /// assert_eq!(petscii, &[0x52, 0x0E, 0x45, 0x8E, 0x12, 0xD3]);
+    /// ```
+    pub fn encode(&mut self, unicode: char, reverse: bool, into: &mut Vec<u8>) -> bool {
+        let codepage = self.codepage;
+
+        let (mode, code) = match codepage.modes[self.state.mode].encode(unicode) {
+            Some(code) => (self.state.mode, code),
+            None => {
+                let switched = codepage.modes
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(mode, dialect)| dialect.encode(unicode).map(|code| (mode, code)))
+                    .filter_map(|(mode, code)| codepage.switches.iter().find(|s| s.mode == Some(mode)).map(|s| (mode, code, s.code)))
+                    .next();
+                match switched {
+                    Some((mode, code, switch)) => {
+                        into.push(switch);
+                        self.state.switch(codepage.switches, switch);
+                        (mode, code)
+                    }
+                    None => return false,
+                }
+            }
+        };
+
+        let code = if codepage.high_bit_reverse {
+            let reversed = reverse && code < 0x80 && !codepage.modes[mode].defined(code | 0x80);
+            self.state.reverse = reversed;
+            if reversed { code | 0x80 } else { code }
+        } else {
+            if reverse != self.state.reverse {
+                if let Some(switch) = codepage.switches.iter().find(|s| s.mode.is_none() && s.reverse == Some(reverse)) {
+                    into.push(switch.code);
+                    self.state.switch(codepage.switches, switch.code);
+                }
+            }
+            code
+        };
+
+        into.push(code);
+        self.state.switch(codepage.switches, code);
+        true
+    }
+
+    /// Encode the specified part of the stream, in the current video, appending it to the specified buffer.
+    ///
+    /// If that fails, the representable prefix is appended nevertheless.
+    pub fn encode_into(&mut self, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        into.reserve(unicode.len());
+        for (char_index, (i, c)) in unicode.char_indices().enumerate() {
+            let reverse = self.state.reverse;
+            if !self.encode(c, reverse, into) {
+                return Err(CodepageError {
+                    representable_up_to: i,
+                    char_index,
+                    unrepresentable: c,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// [PETSCII](https://en.wikipedia.org/wiki/PETSCII) of the Commodore 64,
+/// starting in [unshifted](static.PETSCII_UNSHIFTED.html) mode, and switched to [shifted](static.PETSCII_SHIFTED.html) mode
+/// with `0x0E` and back with `0x8E`.
+///
+/// Reverse video is turned on with `0x12`, and off with `0x92`, as well as by the returns (`0x0D` and `0x8D`).
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use codepage_437::PETSCII;
+/// let mut petscii = vec![];
+/// PETSCII.encode_into("LOAD \"*\",8\rHello", &mut petscii).unwrap();  // H is in both modes, e only in shifted
+/// assert_eq!(petscii,
+///            &[0x4C, 0x4F, 0x41, 0x44, 0x20, 0x22, 0x2A, 0x22, 0x2C, 0x38, 0x0D, 0x48, 0x0E, 0x45, 0x4C, 0x4C, 0x4F]);
+///
+/// let mut unicode = String::new();
+/// PETSCII.decode_into(&petscii, &mut unicode);
+/// assert_eq!(unicode, "LOAD \"*\",8\rHello");
+/// # }
+/// ```
+pub static PETSCII: StatefulCodepage = StatefulCodepage::new(&[&PETSCII_UNSHIFTED, &PETSCII_SHIFTED], PETSCII_SWITCHES, false);
+
+const PETSCII_SWITCHES: &[StateSwitch] = &[
+    StateSwitch { code: 0x0E, mode: Some(1), reverse: None },         // SWITCH TO LOWER CASE
+    StateSwitch { code: 0x8E, mode: Some(0), reverse: None },         // SWITCH TO UPPER CASE
+    StateSwitch { code: 0x12, mode: None, reverse: Some(true) },      // REVERSE ON
+    StateSwitch { code: 0x92, mode: None, reverse: Some(false) },     // REVERSE OFF
+    StateSwitch { code: 0x0D, mode: None, reverse: Some(false) },     // RETURN
+    StateSwitch { code: 0x8D, mode: None, reverse: Some(false) },     // SHIFT RETURN
+];
+
+/// [ATASCII](https://en.wikipedia.org/wiki/ATASCII) of the Atari 8-bit computers,
+/// the [normal video](static.ATASCII_NORMAL.html) characters with the high bit set being their reverse video counterparts.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// # use codepage_437::ATASCII;
+/// let mut encoder = ATASCII.encoder();
+/// let mut atascii = vec![];
+/// encoder.encode_into("READY", &mut atascii).unwrap();
+/// encoder.encode('\n', false, &mut atascii);
+/// encoder.encode(' ', true, &mut atascii);
+/// assert_eq!(atascii, &[0x52, 0x45, 0x41, 0x44, 0x59, 0x9B, 0xA0]);
+///
+/// let mut unicode = String::new();
+/// ATASCII.decode_into(&atascii, &mut unicode);
+/// assert_eq!(unicode, "READY\n ");
+/// # }
+/// ```
+pub static ATASCII: StatefulCodepage = StatefulCodepage::new(&[&ATASCII_NORMAL], &[], true);
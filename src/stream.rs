@@ -0,0 +1,73 @@
+use self::super::{SingleByteCodepage, DoubleByteCodepage, StatefulCodepage, CodepageState, CodepageError};
+use std::fmt;
+
+
+/// Codepages streams can be decoded from and encoded to with [`Cp437Reader`](struct.Cp437Reader.html)
+/// and [`Cp437Writer`](struct.Cp437Writer.html).
+///
+/// Implemented for [`SingleByteCodepage`](struct.SingleByteCodepage.html),
+/// [`DoubleByteCodepage`](struct.DoubleByteCodepage.html), and [`StatefulCodepage`](struct.StatefulCodepage.html).
+pub trait StreamCodepage {
+    /// What's carried across chunks of a stream, starting at its default.
+    type State: Default + fmt::Debug;
+
+    /// Decode the specified chunk of a stream, appending it to the specified string,
+    /// and returning the amount of bytes decoded.
+    ///
+    /// Unless this is the `last` chunk, an incomplete codepoint (of at most a byte) at its end is left undecoded,
+    /// to be passed again at the start of the next one.
+    fn decode_chunk(&self, state: &mut Self::State, data: &[u8], last: bool, into: &mut String) -> usize;
+
+    /// Encode the specified chunk of a stream, appending it to the specified buffer.
+    ///
+    /// If that fails, the representable prefix is appended nevertheless.
+    fn encode_chunk(&self, state: &mut Self::State, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError>;
+}
+
+impl StreamCodepage for SingleByteCodepage {
+    type State = ();
+
+    fn decode_chunk(&self, _: &mut (), data: &[u8], _: bool, into: &mut String) -> usize {
+        self.decode_into(data, into);
+        data.len()
+    }
+
+    fn encode_chunk(&self, _: &mut (), unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        self.encode_into(unicode, into)
+    }
+}
+
+/// A lead byte at the end of the last chunk decodes to U+FFFD REPLACEMENT CHARACTER.
+impl StreamCodepage for DoubleByteCodepage {
+    type State = ();
+
+    fn decode_chunk(&self, _: &mut (), data: &[u8], last: bool, into: &mut String) -> usize {
+        self.decode_impl(data, last, false, into).unwrap()
+    }
+
+    fn encode_chunk(&self, _: &mut (), unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        self.encode_into(unicode, into)
+    }
+}
+
+/// Decoding and encoding carry the mode and reverse video across chunks, separately;
+/// text is encoded in the reverse video the stream is in.
+impl StreamCodepage for StatefulCodepage {
+    type State = CodepageState;
+
+    fn decode_chunk(&self, state: &mut CodepageState, data: &[u8], _: bool, into: &mut String) -> usize {
+        let mut decoder = self.decoder();
+        decoder.set_state(*state);
+        decoder.decode_into(data, into);
+        *state = decoder.state();
+        data.len()
+    }
+
+    fn encode_chunk(&self, state: &mut CodepageState, unicode: &str, into: &mut Vec<u8>) -> Result<(), CodepageError> {
+        let mut encoder = self.encoder();
+        encoder.set_state(*state);
+        let result = encoder.encode_into(unicode, into);
+        *state = encoder.state();
+        result
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(feature = "alloc")]
+use self::super::{SingleByteCodepage, CodepageError};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+
+/// Get the transliteration of a single Unicode codepoint, if one is known.
+///
+/// Transliterations are made up of:
+///   * the base character with combining marks stripped after canonical decomposition (NFD), like `'ź'` → `"z"`,
+///   * ASCII equivalents of typographic punctuation, like `'“'` → `"\""` or `'…'` → `"..."`,
+///   * look-alike symbols and letters, like `'✔'` → `"✓"` or `'ł'` → `"l"`.
+///
+/// Combining marks on their own have no transliteration, but are dropped when transliterating decomposed text;
+/// see [`Transliterate`](trait.Transliterate.html).
+///
+/// The result isn't guaranteed to be representable in any specific dialect,
+/// and may need to go through its variant table, like `'✓'` does.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::transliteration;
+/// assert_eq!(transliteration('ź'), Some("z"));
+/// assert_eq!(transliteration('“'), Some("\""));
+/// assert_eq!(transliteration('…'), Some("..."));
+/// assert_eq!(transliteration('✔'), Some("✓"));
+///
+/// assert_eq!(transliteration('A'), None);
+/// ```
+pub fn transliteration(unicode: char) -> Option<&'static str> {
+    transliteration_table(unicode)
+}
+
+
+/// Convert Unicode data to a single-byte codepage, transliterating characters unrepresentable therein.
+///
+/// Characters representable in the codepage are encoded as they would be with [`ToCodepage`](trait.ToCodepage.html),
+/// the rest is replaced with their [`transliteration()`](fn.transliteration.html), which needs to be representable in full.
+/// Unrepresentable combining diacritical marks (`U+0300..=U+036F`) following a character are dropped,
+/// so decomposed (NFD) text has its accents stripped, even where the precomposed character is representable.
+///
+/// # Examples
+///
+/// Good:
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, Transliterate};
+/// let unicode = "Źdźbło — „Żółć”… ✔";
+///
+/// assert_eq!(unicode.transliterate_to_cp437(&CP437_CONTROL), Ok(b"Zdzblo - \"Z\xA2lc\"... \xFB"[..].into()));  // ó is in cp437
+///
+/// // Decomposed: Z, COMBINING DOT ABOVE, o, COMBINING ACUTE ACCENT; unlike precomposed ó, o and the accent are encoded separately
+/// assert_eq!("Z\u{307}o\u{301}".transliterate_to_cp437(&CP437_CONTROL), Ok(b"Zo"[..].into()));
+/// assert_eq!("Żó".transliterate_to_cp437(&CP437_CONTROL), Ok(b"Z\xA2"[..].into()));
+/// ```
+///
+/// In other codepages:
+///
+/// ```
+/// # use codepage_437::{CP850_CONTROL, Transliterate};
+/// assert_eq!("Łódź".transliterate_to_codepage(&CP850_CONTROL), Ok(b"L\xA2dz"[..].into()));
+/// ```
+///
+/// Untransliterable:
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, Transliterate};
+/// // ジ has no transliteration
+/// let unicode = "Żurek ジ";
+///
+/// let error = unicode.transliterate_to_cp437(&CP437_CONTROL).unwrap_err();
+/// assert_eq!(error.representable_up_to, 7);
+/// ```
+#[cfg(feature = "alloc")]
+pub trait Transliterate<'s, T> {
+    /// Do the conversion.
+    fn transliterate_to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<T, CodepageError>;
+
+    /// Do the conversion; this is [`transliterate_to_codepage()`](#tymethod.transliterate_to_codepage) under its original name.
+    fn transliterate_to_cp437(&'s self, dialect: &SingleByteCodepage) -> Result<T, CodepageError> {
+        self.transliterate_to_codepage(dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'s> Transliterate<'s, Cow<'s, [u8]>> for str {
+    fn transliterate_to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        transliterate_cow_impl(self, dialect)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, S: AsRef<str>> Transliterate<'s, Cow<'s, [u8]>> for S {
+    fn transliterate_to_codepage(&'s self, dialect: &SingleByteCodepage) -> Result<Cow<'s, [u8]>, CodepageError> {
+        transliterate_cow_impl(self.as_ref(), dialect)
+    }
+}
+
+
+#[cfg(feature = "alloc")]
+fn transliterate_cow_impl<'c>(whom: &'c str, dialect: &SingleByteCodepage) -> Result<Cow<'c, [u8]>, CodepageError> {
+    let overlapping = dialect.overlap_unicode_prefix(whom);
+    if overlapping == whom.len() {
+        Ok(Cow::Borrowed(whom.as_bytes()))
+    } else {
+        transliterate_impl_meat(whom, overlapping, dialect).map(Cow::Owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn transliterate_impl_meat(whom: &str, overlapping: usize, dialect: &SingleByteCodepage) -> Result<Vec<u8>, CodepageError> {
+    let mut result = Vec::with_capacity(whom.len());
+    result.extend_from_slice(&whom.as_bytes()[..overlapping]);
+
+    for (char_index, (i, c)) in whom[overlapping..].char_indices().enumerate() {
+        // The overlapping prefix is ASCII, so as many characters as bytes
+        let (char_index, i) = (overlapping + char_index, overlapping + i);
+
+        if let Some(b) = dialect.encode(c) {
+            result.push(b);
+        } else if is_combining_mark(c) && i != 0 {
+            // Stacked on the preceding character, which was already transliterated
+        } else if let Some(transliteration) = transliteration_table(c) {
+            for tc in transliteration.chars() {
+                if let Some(b) = dialect.encode(tc) {
+                    result.push(b);
+                } else {
+                    return Err(CodepageError {
+                        representable_up_to: i,
+                        char_index,
+                        unrepresentable: c,
+                    });
+                }
+            }
+        } else {
+            return Err(CodepageError {
+                representable_up_to: i,
+                char_index,
+                unrepresentable: c,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Check, whether the specified character is in the Combining Diacritical Marks block.
+#[cfg(feature = "alloc")]
+fn is_combining_mark(unicode: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&unicode)
+}
+
+
+include!(concat!(env!("OUT_DIR"), "/transliteration.rs"));
@@ -0,0 +1,227 @@
+use self::super::{SingleByteCodepage, StreamCodepage, CodepageError};
+use std::io::{self, Write};
+use std::str;
+
+
+/// Adapter encoding a stream of UTF-8 bytes into a stream of cp437 bytes.
+///
+/// Multi-byte UTF-8 sequences can be split across multiple `write()` calls, the partial sequence is kept until it's complete.
+///
+/// Writing a character not representable in cp437 fails with an `io::Error` of kind `InvalidData`,
+/// wrapping a `CodepageError` whose `representable_up_to` and `char_index` are the offsets thereof from the start of the stream.
+/// Invalid UTF-8 is rejected with an `io::Error` of the same kind.
+/// Either way, writing can go on after the offending data:
+/// the part of a split character or invalid sequence accepted by an earlier call is dropped alongside it.
+///
+/// Streams can be encoded in [`DoubleByteCodepage`](struct.DoubleByteCodepage.html)s
+/// and [`StatefulCodepage`](struct.StatefulCodepage.html)s too.
+///
+/// Errors returned by the underlying writer after the data was accepted are reported on the next call;
+/// in particular, call `flush()` before dropping the writer to make sure everything was written.
+///
+/// # Examples
+///
+/// ```
+/// # use codepage_437::{CP437_CONTROL, Cp437Error, Cp437Writer};
+/// # use std::io::Write;
+/// let mut cp437 = Vec::new();
+///
+/// {
+///     let mut writer = Cp437Writer::new(&mut cp437, &CP437_CONTROL);
+///     writer.write_all("Local news\n".as_bytes()).unwrap();
+///     writer.write_all(&"₧½ million".as_bytes()[..2]).unwrap();
+///     writer.write_all(&"₧½ million".as_bytes()[2..]).unwrap();
+///
+///     let error = writer.write_all("; Jurek żelaznym".as_bytes()).unwrap_err();
+///     let error = error.get_ref().unwrap().downcast_ref::<Cp437Error>().unwrap();
+///     assert_eq!((error.representable_up_to, error.char_index, error.unrepresentable), (32, 29, 'ż'));
+///
+///     writer.flush().unwrap();
+/// }
+///
+/// assert_eq!(cp437, &[0x4C, 0x6F, 0x63, 0x61, 0x6C, 0x20, 0x6E, 0x65, 0x77, 0x73, 0x0A,
+///                     0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E,
+///                     0x3B, 0x20, 0x4A, 0x75, 0x72, 0x65, 0x6B, 0x20]);
+/// ```
+#[derive(Debug)]
+pub struct Cp437Writer<'d, W: Write, D: StreamCodepage + ?Sized + 'd = SingleByteCodepage> {
+    inner: W,
+    dialect: &'d D,
+    state: D::State,
+
+    position: usize,
+    encoded: usize,
+    partial: [u8; 4],
+    partial_len: usize,
+    pending: Vec<u8>,
+}
+
+impl<'d, W: Write, D: StreamCodepage + ?Sized> Cp437Writer<'d, W, D> {
+    /// Create a new encoding adapter around the specified writer.
+    pub fn new(inner: W, dialect: &'d D) -> Cp437Writer<'d, W, D> {
+        Cp437Writer {
+            inner,
+            dialect,
+            state: D::State::default(),
+
+            position: 0,
+            encoded: 0,
+            partial: [0; 4],
+            partial_len: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to it may interleave with encoded data not yet written out by this adapter.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap this adapter, returning the underlying writer.
+    ///
+    /// Encoded data that couldn't yet be written out, as well as a trailing incomplete UTF-8 sequence, is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn flush_pending(&mut self) -> io::Result<()> {
+        while !self.pending.is_empty() {
+            match self.inner.write(&self.pending) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write encoded cp437 data")),
+                Ok(n) => {
+                    self.pending.drain(..n);
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode as much of `buf` as possible into `pending`,
+    /// returning the amount of bytes consumed and the error that stopped consumption, if any.
+    fn encode(&mut self, buf: &[u8]) -> (usize, Option<io::Error>) {
+        let mut consumed = 0;
+
+        if self.partial_len != 0 {
+            let width = utf8_width(self.partial[0]);
+            let taken = (width - self.partial_len).min(buf.len());
+
+            let mut sequence = self.partial;
+            sequence[self.partial_len..self.partial_len + taken].copy_from_slice(&buf[..taken]);
+            match str::from_utf8(&sequence[..self.partial_len + taken]) {
+                Ok(c) => {
+                    if let Err(e) = self.encode_str(c) {
+                        // The start of the character was already accepted, so it's skipped as a whole, like it would be by the caller
+                        let error = unrepresentable(self.position - self.partial_len, self.encoded, e.unrepresentable);
+                        self.partial_len = 0;
+                        self.position += taken;
+                        return (0, Some(error));
+                    }
+                }
+                Err(e) => {
+                    if e.error_len().is_some() {
+                        self.partial_len = 0;
+                        return (0, Some(invalid_utf8()));
+                    }
+
+                    self.partial = sequence;
+                    self.partial_len += taken;
+                    self.position += taken;
+                    return (taken, None);
+                }
+            }
+
+            self.partial_len = 0;
+            self.position += taken;
+            consumed = taken;
+        }
+
+        let (valid, error) = match str::from_utf8(&buf[consumed..]) {
+            Ok(s) => (s, None),
+            Err(e) => (str::from_utf8(&buf[consumed..consumed + e.valid_up_to()]).unwrap(), Some(e)),
+        };
+
+        if let Err(e) = self.encode_str(valid) {
+            self.position += e.representable_up_to;
+            return (consumed + e.representable_up_to, Some(unrepresentable(self.position, self.encoded, e.unrepresentable)));
+        }
+        consumed += valid.len();
+        self.position += valid.len();
+
+        match error {
+            Some(e) if e.error_len().is_none() => {
+                let tail = &buf[consumed..];
+                self.partial[..tail.len()].copy_from_slice(tail);
+                self.partial_len = tail.len();
+                self.position += tail.len();
+                (buf.len(), None)
+            }
+            Some(_) => (consumed, Some(invalid_utf8())),
+            None => (consumed, None),
+        }
+    }
+
+    /// Encode the specified string into `pending`.
+    ///
+    /// If that fails, the representable prefix is encoded nevertheless, and the error, relative to `s`, returned.
+    fn encode_str(&mut self, s: &str) -> Result<(), CodepageError> {
+        let result = self.dialect.encode_chunk(&mut self.state, s, &mut self.pending);
+        // Counted in characters, since in double-byte codepages they're not all a byte
+        self.encoded += match result {
+            Ok(()) => s.chars().count(),
+            Err(ref e) => e.char_index,
+        };
+        result
+    }
+}
+
+impl<'d, W: Write, D: StreamCodepage + ?Sized> Write for Cp437Writer<'d, W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.flush_pending()?;
+
+        match self.encode(buf) {
+            (0, Some(e)) => Err(e),
+            (consumed, _) => {
+                // Data was accepted, so an error here will resurface on the next call
+                let _ = self.flush_pending();
+                Ok(consumed)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_pending()?;
+        self.inner.flush()
+    }
+}
+
+
+fn utf8_width(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+fn unrepresentable(offset: usize, char_index: usize, unrepresentable: char) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData,
+                   CodepageError {
+                       representable_up_to: offset,
+                       char_index,
+                       unrepresentable,
+                   })
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
@@ -2,8 +2,12 @@ use self::super::super::ALL_CP437;
 use codepage_437::CP437_CONTROL;
 use self::super:: ALL_UTF8;
 
+#[cfg(feature = "alloc")]
 mod borrow_from_cp437;
+#[cfg(feature = "alloc")]
 mod from_cp437;
+#[cfg(feature = "std")]
+mod reader;
 
 
 #[test]
@@ -0,0 +1,43 @@
+use codepage_437::{CP437_CONTROL, Cp437Reader};
+use self::super::super::super::ALL_CP437;
+use std::io::{BufRead, Read};
+use self::super::super::ALL_UTF8;
+
+
+#[test]
+fn read_to_string() {
+    let mut unicode = String::new();
+    Cp437Reader::new(ALL_CP437, &CP437_CONTROL).read_to_string(&mut unicode).unwrap();
+    assert_eq!(unicode, ALL_UTF8);
+}
+
+#[test]
+fn split_reads() {
+    for capacity in 1..8 {
+        let mut reader = Cp437Reader::with_capacity(capacity, ALL_CP437, &CP437_CONTROL);
+
+        let mut unicode = vec![];
+        let mut buf = [0u8; 1];
+        while reader.read(&mut buf).unwrap() != 0 {
+            unicode.push(buf[0]);
+        }
+
+        assert_eq!(String::from_utf8(unicode).unwrap(), ALL_UTF8);
+    }
+}
+
+#[test]
+fn lines() {
+    let expected: Vec<_> = ALL_UTF8.split('\n').collect();
+    let lines: Vec<_> = Cp437Reader::with_capacity(3, ALL_CP437, &CP437_CONTROL).lines().map(Result::unwrap).collect();
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn into_inner() {
+    let mut reader = Cp437Reader::with_capacity(0x10, ALL_CP437, &CP437_CONTROL);
+    reader.read_exact(&mut [0u8; 1]).unwrap();
+
+    assert_eq!(reader.get_ref().len(), ALL_CP437.len() - 0x10);
+    assert_eq!(reader.into_inner(), &ALL_CP437[0x10..]);
+}
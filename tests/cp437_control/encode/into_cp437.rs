@@ -1,6 +1,9 @@
 use self::super::super::{VARIANTS_CP437, VARIANTS_UTF8, ALL_UTF8};
-use codepage_437::{CP437_CONTROL, Cp437Error, IntoCp437};
+use codepage_437::{CP437_CONTROL, ReplacementPolicy, Cp437Error, IntoCp437};
 use self::super::super::super::ALL_CP437;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::rc::Rc;
 
 
 #[test]
@@ -17,6 +20,77 @@ fn unrepresentable() {
     let err = "Jurek je żurek w żupanie.".to_string().into_cp437(&CP437_CONTROL).unwrap_err();
 
     assert_eq!(err.as_str(), "Jurek je żurek w żupanie.");
-    assert_eq!(err.cp437_error(), Cp437Error { representable_up_to: 9 });
+    assert_eq!(err.cp437_error(), Cp437Error { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' });
     assert_eq!(err.into_string(), "Jurek je żurek w żupanie.");
 }
+
+#[test]
+fn lossy_good() {
+    let everything_utf8 = ALL_UTF8.to_string() + VARIANTS_UTF8;
+    let mut everything_cp437 = ALL_CP437.to_vec();
+    everything_cp437.extend(VARIANTS_CP437);
+
+    let result = everything_utf8.into_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Skip);
+    assert_eq!(result.encoded, everything_cp437);
+    assert_eq!(result.replaced, []);
+}
+
+#[test]
+fn lossy_unrepresentable() {
+    let result = "Jurek je żurek w żupanie.".to_string().into_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(b'?'));
+
+    assert_eq!(result.encoded, b"Jurek je ?urek w ?upanie.");
+    assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+}
+
+#[test]
+fn boxed_str() {
+    let cp437: Result<Vec<u8>, _> = ALL_UTF8.to_string().into_boxed_str().into_cp437(&CP437_CONTROL);
+    assert_eq!(cp437, Ok(ALL_CP437.to_vec()));
+
+    let err = "Jurek je żurek w żupanie.".to_string().into_boxed_str().into_cp437(&CP437_CONTROL).unwrap_err();
+    assert_eq!(err.into_string(), "Jurek je żurek w żupanie.");
+}
+
+#[test]
+fn cow() {
+    assert_eq!(Cow::Borrowed(ALL_UTF8).into_cp437(&CP437_CONTROL), Ok(ALL_CP437.to_vec()));
+    assert_eq!(Cow::<str>::Owned(ALL_UTF8.to_string()).into_cp437(&CP437_CONTROL), Ok(ALL_CP437.to_vec()));
+
+    let err = Cow::Borrowed("Jurek je żurek w żupanie.").into_cp437(&CP437_CONTROL).unwrap_err();
+    assert_eq!(err.cp437_error(), Cp437Error { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' });
+    assert_eq!(err.unrepresentable(), [(9, 'ż'), (18, 'ż')]);
+}
+
+#[test]
+fn shared() {
+    assert_eq!(Rc::<str>::from(ALL_UTF8).into_cp437(&CP437_CONTROL), Ok(Rc::from(ALL_CP437)));
+    assert_eq!(Arc::<str>::from(ALL_UTF8).into_cp437(&CP437_CONTROL), Ok(Arc::from(ALL_CP437)));
+
+    let unicode = Rc::<str>::from("Local news");
+    let data = unicode.as_ptr();
+    assert_eq!(unicode.into_cp437(&CP437_CONTROL).unwrap().as_ptr(), data);
+
+    let err = Arc::<str>::from("Jurek je żurek w żupanie.").into_cp437(&CP437_CONTROL).unwrap_err();
+    assert_eq!(err.as_str(), "Jurek je żurek w żupanie.");
+
+    let result = Rc::<str>::from("Jurek je żurek w żupanie.").into_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(b'?'));
+    assert_eq!(&*result.encoded, b"Jurek je ?urek w ?upanie.");
+    assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+}
+
+#[cfg(feature = "smartstring")]
+#[test]
+fn smartstring() {
+    let unicode: smartstring::alias::String = ALL_UTF8.into();
+    assert_eq!(unicode.into_cp437(&CP437_CONTROL), Ok(ALL_CP437.to_vec()));
+}
+
+#[cfg(feature = "compact_str")]
+#[test]
+fn compact_str() {
+    assert_eq!(compact_str::CompactString::from(ALL_UTF8).into_cp437(&CP437_CONTROL), Ok(ALL_CP437.to_vec()));
+
+    let err = compact_str::CompactString::from("Eżektor").into_cp437(&CP437_CONTROL).unwrap_err();
+    assert_eq!(err.into_string(), "Eżektor");
+}
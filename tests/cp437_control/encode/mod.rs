@@ -1,3 +1,8 @@
+#[cfg(feature = "alloc")]
 mod into_cp437;
+#[cfg(feature = "alloc")]
 mod to_cp437;
+#[allow(clippy::module_inception)]
 mod encode;
+#[cfg(feature = "std")]
+mod writer;
@@ -9,7 +9,7 @@ fn borrowed_for_ascii() {
         data.push(c);
 
         assert!(is_borrowed(&data.to_cp437(&CP437_CONTROL).unwrap()));
-        assert!(is_borrowed(&[c].into_iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
+        assert!(is_borrowed(&[c].iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
     }
 }
 
@@ -20,7 +20,7 @@ fn owned_for_beyond_ascii() {
         data.push(c);
 
         assert!(!is_borrowed(&data.to_cp437(&CP437_CONTROL).unwrap()));
-        assert!(!is_borrowed(&[c].into_iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
+        assert!(!is_borrowed(&[c].iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
     }
 }
 
@@ -39,6 +39,6 @@ fn owned_for_both() {
         data.push(new_beyond);
 
         assert!(!is_borrowed(&data.to_cp437(&CP437_CONTROL).unwrap()));
-        assert!(!is_borrowed(&[c, new_beyond].into_iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
+        assert!(!is_borrowed(&[c, new_beyond].iter().collect::<String>().to_cp437(&CP437_CONTROL).unwrap()));
     }
 }
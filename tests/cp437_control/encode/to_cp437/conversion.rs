@@ -1,5 +1,5 @@
 use self::super::super::super::{VARIANTS_CP437, VARIANTS_UTF8, ALL_UTF8};
-use codepage_437::{CP437_CONTROL, Cp437Error, ToCp437};
+use codepage_437::{CP437_CONTROL, ReplacementPolicy, Cp437Error, ToCp437};
 use self::super::super::super::super::ALL_CP437;
 
 
@@ -15,5 +15,40 @@ fn good() {
 
 #[test]
 fn unrepresentable() {
-    assert_eq!("Jurek je żurek w żupanie.".to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 9 }));
+    assert_eq!("Jurek je żurek w żupanie.".to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' }));
+}
+
+#[test]
+fn lossy_good() {
+    let everything_utf8 = ALL_UTF8.to_string() + VARIANTS_UTF8;
+    let mut everything_cp437 = ALL_CP437.to_vec();
+    everything_cp437.extend(VARIANTS_CP437);
+
+    let result = everything_utf8.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Skip);
+    assert_eq!(result.encoded, &everything_cp437[..]);
+    assert_eq!(result.replaced, []);
+}
+
+#[test]
+fn lossy_unrepresentable() {
+    let unicode = "Jurek je żurek w żupanie, Łukasz też.";
+    let replaced = [(9, 'ż'), (18, 'ż'), (28, 'Ł'), (38, 'ż')];
+
+    let result = unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Substitute(0xFE));
+    assert_eq!(result.encoded, &b"Jurek je \xFEurek w \xFEupanie, \xFEukasz te\xFE."[..]);
+    assert_eq!(result.replaced, replaced);
+
+    let result = unicode.to_cp437_lossy(&CP437_CONTROL, ReplacementPolicy::Skip);
+    assert_eq!(result.encoded, &b"Jurek je urek w upanie, ukasz te."[..]);
+    assert_eq!(result.replaced, replaced);
+
+    let mut seen = vec![];
+    let result = unicode.to_cp437_lossy(&CP437_CONTROL,
+                                        ReplacementPolicy::With(&mut |c| {
+                                            seen.push(c);
+                                            if c == 'ż' { Some(b"zh".to_vec()) } else { None }
+                                        }));
+    assert_eq!(result.encoded, &b"Jurek je zhurek w zhupanie, ukasz tezh."[..]);
+    assert_eq!(result.replaced, replaced);
+    assert_eq!(seen, ['ż', 'ż', 'Ł', 'ż']);
 }
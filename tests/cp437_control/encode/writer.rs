@@ -0,0 +1,91 @@
+use self::super::super::{VARIANTS_CP437, VARIANTS_UTF8, ALL_UTF8};
+use codepage_437::{CP437_CONTROL, Cp437Error, Cp437Writer};
+use self::super::super::super::ALL_CP437;
+use std::io::{ErrorKind, Write};
+
+
+#[test]
+fn good() {
+    let everything_utf8 = ALL_UTF8.to_string() + VARIANTS_UTF8;
+    let mut everything_cp437 = ALL_CP437.to_vec();
+    everything_cp437.extend(VARIANTS_CP437);
+
+    for chunk_size in 1..8 {
+        let mut cp437 = vec![];
+        {
+            let mut writer = Cp437Writer::new(&mut cp437, &CP437_CONTROL);
+            for chunk in everything_utf8.as_bytes().chunks(chunk_size) {
+                writer.write_all(chunk).unwrap();
+            }
+            writer.flush().unwrap();
+        }
+
+        assert_eq!(cp437, everything_cp437);
+    }
+}
+
+#[test]
+fn unrepresentable() {
+    let unicode = "Jurek je żurek w żupanie.";
+
+    for chunk_size in 1..8 {
+        let mut cp437 = vec![];
+        {
+            let mut writer = Cp437Writer::new(&mut cp437, &CP437_CONTROL);
+
+            let error = unicode.as_bytes().chunks(chunk_size).map(|chunk| writer.write_all(chunk)).find(Result::is_err).unwrap().unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.get_ref().unwrap().downcast_ref::<Cp437Error>(), Some(&Cp437Error { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' }));
+
+            writer.flush().unwrap();
+        }
+
+        assert_eq!(cp437, b"Jurek je ");
+    }
+}
+
+#[test]
+fn invalid_utf8() {
+    for data in &[&b"Jurek\xFF"[..], &b"Jurek\xC5\x41"[..], &b"Jurek\xC5"[..]] {
+        let mut cp437 = vec![];
+        {
+            let mut writer = Cp437Writer::new(&mut cp437, &CP437_CONTROL);
+
+            let mut error = writer.write_all(data).err();
+            if error.is_none() {
+                error = writer.write_all(b"A").err();
+            }
+            assert_eq!(error.unwrap().kind(), ErrorKind::InvalidData);
+
+            writer.flush().unwrap();
+        }
+
+        assert_eq!(cp437, b"Jurek");
+    }
+}
+
+#[test]
+fn split_error() {
+    let mut cp437 = vec![];
+    {
+        let mut writer = Cp437Writer::new(&mut cp437, &CP437_CONTROL);
+
+        assert_eq!(writer.write(b"a\xC5").unwrap(), 2);
+        let error = writer.write(b"\xBCb").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.get_ref().unwrap().downcast_ref::<Cp437Error>(), Some(&Cp437Error { representable_up_to: 1, char_index: 1, unrepresentable: 'ż' }));
+        writer.write_all(b"b").unwrap();
+
+        assert_eq!(writer.write(b"\xC5").unwrap(), 1);
+        assert_eq!(writer.write(b"c").unwrap_err().kind(), ErrorKind::InvalidData);
+        writer.write_all(b"c").unwrap();
+
+        let error = writer.write_all("żd".as_bytes()).unwrap_err();
+        assert_eq!(error.get_ref().unwrap().downcast_ref::<Cp437Error>(), Some(&Cp437Error { representable_up_to: 6, char_index: 3, unrepresentable: 'ż' }));
+        writer.write_all(b"d").unwrap();
+
+        writer.flush().unwrap();
+    }
+
+    assert_eq!(cp437, b"abcd");
+}
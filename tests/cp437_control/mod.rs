@@ -1,3 +1,4 @@
+#[cfg(feature = "alloc")]
 use std::borrow::Cow;
 
 mod decode;
@@ -10,9 +11,11 @@ const VARIANTS_CP437: &[u8] = include_bytes!("../../test-data/cp437_control/vari
 const VARIANTS_UTF8: &str = include_str!("../../test-data/cp437_control/variants.utf8");
 
 
+#[cfg(feature = "alloc")]
+#[allow(clippy::ptr_arg)]
 fn is_borrowed<T: ToOwned + ?Sized>(who: &Cow<T>) -> bool {
-    match who {
-        &Cow::Borrowed(_) => true,
-        &Cow::Owned(_) => false,
+    match *who {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
     }
 }
@@ -1,6 +1,7 @@
 use codepage_437::{CP437_WINGDINGS, FromCp437};
 use self::super::super::super::ALL_CP437;
 use self::super::super::ALL_UTF8;
+use std::borrow::Cow;
 
 
 macro_rules! array_test {
@@ -20,6 +21,23 @@ fn vec() {
     assert_eq!(String::from_cp437(ALL_CP437.to_vec(), &CP437_WINGDINGS), ALL_UTF8);
 }
 
+#[test]
+fn boxed_slice() {
+    assert_eq!(String::from_cp437(ALL_CP437.to_vec().into_boxed_slice(), &CP437_WINGDINGS), ALL_UTF8);
+}
+
+#[test]
+fn cow() {
+    assert_eq!(String::from_cp437(Cow::Borrowed(ALL_CP437), &CP437_WINGDINGS), ALL_UTF8);
+    assert_eq!(String::from_cp437(Cow::<[u8]>::Owned(ALL_CP437.to_vec()), &CP437_WINGDINGS), ALL_UTF8);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    assert_eq!(String::from_cp437(bytes::Bytes::from_static(ALL_CP437), &CP437_WINGDINGS), ALL_UTF8);
+}
+
 
 array_test!(array_0, 0);
 array_test!(array_1, 1);
@@ -54,3 +72,5 @@ array_test!(array_29, 29);
 array_test!(array_30, 30);
 array_test!(array_31, 31);
 array_test!(array_32, 32);
+array_test!(array_128, 128);
+array_test!(array_256, 256);
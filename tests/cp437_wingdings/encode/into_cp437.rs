@@ -1,5 +1,5 @@
 use self::super::super::{VARIANTS_CP437, VARIANTS_UTF8, ALL_UTF8};
-use codepage_437::{CP437_WINGDINGS, Cp437Error, IntoCp437};
+use codepage_437::{CP437_WINGDINGS, ReplacementPolicy, Cp437Error, IntoCp437};
 use self::super::super::super::ALL_CP437;
 
 
@@ -17,6 +17,25 @@ fn unrepresentable() {
     let err = "Jurek je żurek w żupanie.".to_string().into_cp437(&CP437_WINGDINGS).unwrap_err();
 
     assert_eq!(err.as_str(), "Jurek je żurek w żupanie.");
-    assert_eq!(err.cp437_error(), Cp437Error { representable_up_to: 9 });
+    assert_eq!(err.cp437_error(), Cp437Error { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' });
     assert_eq!(err.into_string(), "Jurek je żurek w żupanie.");
 }
+
+#[test]
+fn lossy_good() {
+    let everything_utf8 = ALL_UTF8.to_string() + VARIANTS_UTF8;
+    let mut everything_cp437 = ALL_CP437.to_vec();
+    everything_cp437.extend(VARIANTS_CP437);
+
+    let result = everything_utf8.into_cp437_lossy(&CP437_WINGDINGS, ReplacementPolicy::Skip);
+    assert_eq!(result.encoded, everything_cp437);
+    assert_eq!(result.replaced, []);
+}
+
+#[test]
+fn lossy_unrepresentable() {
+    let result = "Jurek je żurek w żupanie.".to_string().into_cp437_lossy(&CP437_WINGDINGS, ReplacementPolicy::Substitute(b'?'));
+
+    assert_eq!(result.encoded, b"Jurek je ?urek w ?upanie.");
+    assert_eq!(result.replaced, [(9, 'ż'), (18, 'ż')]);
+}
@@ -0,0 +1,100 @@
+/// Test a double-byte dialect against `test-data/$data/sample.{dbcs,utf8}`, and all its codepoints round-tripping.
+macro_rules! double_byte_tests {
+    ($name:ident, $dialect:ident, $data:literal) => {
+        mod $name {
+            use codepage_437::$dialect;
+            #[cfg(feature = "alloc")]
+            use codepage_437::{BorrowFromDbcs, FromDbcs, ToDbcs};
+            #[cfg(feature = "std")]
+            use codepage_437::{Cp437Reader, Cp437Writer};
+            #[cfg(feature = "std")]
+            use std::io::{Read, Write};
+            #[cfg(feature = "alloc")]
+            use std::borrow::Cow;
+
+
+            #[cfg(feature = "alloc")]
+            const SAMPLE_DBCS: &[u8] = include_bytes!(concat!("../../test-data/", $data, "/sample.dbcs"));
+            #[cfg(feature = "alloc")]
+            const SAMPLE_UTF8: &str = include_str!(concat!("../../test-data/", $data, "/sample.utf8"));
+
+
+            #[test]
+            fn decode() {
+                for code in 0..=0xFFFF {
+                    if let Some(c) = $dialect.decode(code) {
+                        assert_eq!($dialect.decode($dialect.encode(c).unwrap()), Some(c), "0x{:04X}", code);
+                    }
+                }
+                for b in 0..=0xFF {
+                    if $dialect.is_lead(b) {
+                        assert_eq!($dialect.decode(b as u16), None);
+                    }
+                }
+            }
+
+            #[test]
+            fn encode() {
+                for code in 0..=0xFFFF {
+                    if let Some(code) = $dialect.decode(code).and_then(|c| $dialect.encode(c)) {
+                        assert_eq!($dialect.encode($dialect.decode(code).unwrap()), Some(code));
+                        assert_eq!(code <= 0xFF, !$dialect.is_lead((code >> 8) as u8));
+                    }
+                }
+                assert_eq!($dialect.encode('A'), Some(0x41));
+                assert_eq!($dialect.encode('☺'), None);
+                assert_eq!($dialect.encode('\u{1F600}'), None);
+            }
+
+            #[test]
+            #[cfg(feature = "alloc")]
+            fn conversion() {
+                assert_eq!(String::from_dbcs(SAMPLE_DBCS.to_vec(), &$dialect), SAMPLE_UTF8);
+                assert_eq!(String::from_dbcs(SAMPLE_DBCS.to_vec().into_boxed_slice(), &$dialect), SAMPLE_UTF8);
+                assert_eq!(String::from_dbcs(Cow::Borrowed(SAMPLE_DBCS), &$dialect), SAMPLE_UTF8);
+                assert_eq!(String::try_from_dbcs(SAMPLE_DBCS.to_vec(), &$dialect), Ok(SAMPLE_UTF8.to_string()));
+                assert_eq!(String::borrow_from_dbcs(SAMPLE_DBCS, &$dialect), SAMPLE_UTF8);
+                assert_eq!(Cow::try_borrow_from_dbcs(SAMPLE_DBCS, &$dialect), Ok(Cow::Borrowed(SAMPLE_UTF8)));
+                assert_eq!(SAMPLE_UTF8.to_dbcs(&$dialect), Ok(Cow::Borrowed(SAMPLE_DBCS)));
+            }
+
+            #[test]
+            #[cfg(feature = "alloc")]
+            fn borrowing() {
+                let ascii: Vec<u8> = (0x20..0x7F).collect();
+                let unicode = String::from_utf8(ascii.clone()).unwrap();
+
+                assert!(matches!(Cow::borrow_from_dbcs(&ascii[..], &$dialect), Cow::Borrowed(_)));
+                assert!(matches!(unicode.to_dbcs(&$dialect), Ok(Cow::Borrowed(_))));
+            }
+
+            #[test]
+            #[cfg(feature = "std")]
+            fn stream() {
+                for capacity in 1..=5 {
+                    let mut unicode = String::new();
+                    Cp437Reader::with_capacity(capacity, SAMPLE_DBCS, &$dialect).read_to_string(&mut unicode).unwrap();
+                    assert_eq!(unicode, SAMPLE_UTF8);
+                }
+
+                let mut written = vec![];
+                {
+                    let mut writer = Cp437Writer::new(&mut written, &$dialect);
+                    for chunk in SAMPLE_UTF8.as_bytes().chunks(5) {
+                        writer.write_all(chunk).unwrap();
+                    }
+                }
+                assert_eq!(written, SAMPLE_DBCS);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod truncated;
+
+
+double_byte_tests!(cp932, CP932, "cp932");
+double_byte_tests!(cp936, CP936, "cp936");
+double_byte_tests!(cp949, CP949, "cp949");
+double_byte_tests!(cp950, CP950, "cp950");
@@ -0,0 +1,83 @@
+use codepage_437::{CP932, CP936, DecodeError, BorrowFromDbcs, FromDbcs};
+#[cfg(feature = "std")]
+use codepage_437::Cp437Reader;
+#[cfg(feature = "std")]
+use std::io::{BufRead, Read};
+use std::borrow::Cow;
+
+
+// 日本, with the trail byte of 本 missing
+const TRUNCATED: &[u8] = &[0x93, 0xFA, 0x96];
+
+
+#[test]
+fn lossy() {
+    assert_eq!(String::from_dbcs(TRUNCATED.to_vec(), &CP932), "日\u{FFFD}");
+    assert_eq!(String::from_dbcs([0x93, 0xFA, 0x96], &CP932), "日\u{FFFD}");
+    assert_eq!(String::borrow_from_dbcs(TRUNCATED, &CP932), "日\u{FFFD}");
+    assert_eq!(Cow::borrow_from_dbcs(TRUNCATED, &CP932), "日\u{FFFD}");
+    assert_eq!(String::from_dbcs(vec![0x96], &CP932), "\u{FFFD}");
+
+    let mut unicode = String::new();
+    CP932.decode_into(TRUNCATED, &mut unicode);
+    assert_eq!(unicode, "日\u{FFFD}");
+}
+
+#[test]
+fn strict() {
+    let error = DecodeError {
+        defined_up_to: 2,
+        undefined: 0x96,
+    };
+
+    assert_eq!(String::try_from_dbcs(TRUNCATED.to_vec(), &CP932), Err(error));
+    assert_eq!(String::try_from_dbcs(Cow::Borrowed(TRUNCATED), &CP932), Err(error));
+    assert_eq!(String::try_borrow_from_dbcs(TRUNCATED, &CP932), Err(error));
+    assert_eq!(Cow::try_borrow_from_dbcs(TRUNCATED, &CP932), Err(error));
+
+    assert_eq!(String::try_from_dbcs(TRUNCATED[..2].to_vec(), &CP932), Ok("日".to_string()));
+}
+
+#[test]
+fn stray_lead_byte() {
+    // 0x96 followed by ASCII isn't a pair, so the ASCII character is kept
+    assert_eq!(String::from_dbcs(vec![0x96, 0x0A, 0x41], &CP932), "\u{FFFD}\nA");
+    assert_eq!(String::try_from_dbcs(vec![0x41, 0x96, 0x0A], &CP932),
+               Err(DecodeError {
+                   defined_up_to: 1,
+                   undefined: 0x96,
+               }));
+
+    // 0x85 is a lead byte, but nothing's mapped thereafter, and 0xA0 isn't ASCII, so the pair is replaced as a whole
+    assert_eq!(String::from_dbcs(vec![0x85, 0xA0, 0x41], &CP932), "\u{FFFD}A");
+
+    // 0xFF isn't a lead byte, and is undefined in cp936
+    assert_eq!(String::from_dbcs(vec![0xFF, 0x41], &CP936), "\u{FFFD}A");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_split() {
+    let cp932 = [0x93, 0xFA, 0x96, 0x7B, 0x8C, 0xEA];
+
+    // Every chunk boundary falls between a lead byte and its trail byte at some point
+    for capacity in 1..=cp932.len() {
+        let mut unicode = String::new();
+        Cp437Reader::with_capacity(capacity, &cp932[..], &CP932).read_to_string(&mut unicode).unwrap();
+        assert_eq!(unicode, "日本語");
+    }
+
+    // The lead byte comes in a chunk of its own, which mustn't look like the end of the stream
+    let mut reader = Cp437Reader::with_capacity(1, &cp932[..], &CP932);
+    assert_eq!(reader.fill_buf().unwrap(), "日".as_bytes());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_truncated() {
+    for capacity in 1..=TRUNCATED.len() {
+        let mut unicode = String::new();
+        Cp437Reader::with_capacity(capacity, TRUNCATED, &CP932).read_to_string(&mut unicode).unwrap();
+        assert_eq!(unicode, "日\u{FFFD}");
+    }
+}
@@ -0,0 +1,142 @@
+use codepage_437::{CP437_CONTROL, CP850_WINGDINGS, CP857_CONTROL, SingleByteCodepageBuilder, BuildError, FromCodepage, ToCodepage};
+use self::super::ALL_DIALECTS;
+use std::borrow::Cow;
+
+
+#[test]
+fn from_scratch() {
+    let dialect = SingleByteCodepageBuilder::new().build().unwrap();
+
+    for b in 0..=255 {
+        assert_eq!(dialect.decode(b), b as char);
+        assert_eq!(dialect.encode(b as char), Some(b));
+        assert!(!dialect.overlap_cp437(b));
+    }
+    assert_eq!(dialect.encode('Ā'), None);
+}
+
+#[test]
+fn same_as_dialect() {
+    for dialect in ALL_DIALECTS {
+        let built = SingleByteCodepageBuilder::from_dialect(dialect).build();
+        assert!(built.is_ok(), "{:?}: {:?}", dialect, built);
+        let built = built.unwrap();
+
+        assert!(built.diff(dialect).is_empty(), "{:?}: {}", dialect, built.diff(dialect));
+        for b in 0..=255 {
+            assert_eq!(built.defined(b), dialect.defined(b));
+            assert_eq!(built.round_trips(b), dialect.round_trips(b));
+            assert_eq!(built.overlap_cp437(b), dialect.overlap_cp437(b));
+        }
+        for c in "√✓ßΣ€ŻA\u{7F}".chars() {
+            assert_eq!(built.encode(c), dialect.encode(c));
+        }
+    }
+}
+
+#[test]
+fn holes() {
+    // 0xD5 is a hole decoding to U+00D5, which is at 0xE5
+    let built = SingleByteCodepageBuilder::from_dialect(&CP857_CONTROL).build().unwrap();
+    assert!(!built.defined(0xD5));
+    assert_eq!(built.encode('Õ'), Some(0xE5));
+
+    let built = SingleByteCodepageBuilder::from_dialect(&CP857_CONTROL).map(0xD5, '€').map(0xE7, 'Ŧ').map(0xF2, 'ŧ').build().unwrap();
+    assert_eq!(built.encode('€'), Some(0xD5));
+    assert_eq!(built.encode('Õ'), Some(0xE5));
+}
+
+#[test]
+fn map() {
+    let built = SingleByteCodepageBuilder::from_dialect(&CP437_CONTROL).map(0x9E, 'Ŧ').build().unwrap();
+
+    assert_eq!(built.decode(0x9E), 'Ŧ');
+    assert_eq!(built.encode('Ŧ'), Some(0x9E));
+    assert_eq!(built.encode('₧'), None);
+    assert_eq!(String::from_codepage(vec![0x31, 0x9E], &built), "1Ŧ");
+}
+
+#[test]
+fn alias() {
+    let built = SingleByteCodepageBuilder::new().alias('Ā', 0x41).alias('Ă', 0x41).alias('Ą', 0x41).unalias('Ă').build().unwrap();
+
+    assert_eq!("ĀĄA".to_codepage(&built), Ok(Cow::Owned(vec![0x41, 0x41, 0x41])));
+    assert_eq!(built.encode('Ă'), None);
+    assert_eq!(built.decode(0x41), 'A');
+
+    let built = SingleByteCodepageBuilder::new().alias('Ā', 0x41).alias('Ā', 0x42).build().unwrap();
+    assert_eq!(built.encode('Ā'), Some(0x42));
+}
+
+#[test]
+fn overlap() {
+    let built = SingleByteCodepageBuilder::from_dialect(&CP437_CONTROL).map(0x7F, '⌂').overlap(0x00..=0x7E).build().unwrap();
+
+    assert!(built.overlap_unicode('A'));
+    assert!(!built.overlap_unicode('\u{7F}'));
+    assert!(!built.overlap_cp437(0x7F));
+    assert_eq!("Local news".to_codepage(&built), Ok(Cow::Borrowed(&b"Local news"[..])));
+}
+
+#[test]
+fn ambiguous_decode() {
+    assert_eq!(SingleByteCodepageBuilder::new().map(0x80, 'A').build(),
+               Err(BuildError::Ambiguous {
+                   unicode: 'A',
+                   first: 0x41,
+                   second: 0x80,
+               }));
+
+    // Both 0x14 and 0xF4 decode to ¶, which is aliased to 0xF4
+    let built = SingleByteCodepageBuilder::from_dialect(&CP850_WINGDINGS).build().unwrap();
+    assert_eq!(built.decode(0x14), '¶');
+    assert_eq!(built.encode('¶'), Some(0xF4));
+    assert_eq!(SingleByteCodepageBuilder::from_dialect(&CP850_WINGDINGS).unalias('¶').build(),
+               Err(BuildError::Ambiguous {
+                   unicode: '¶',
+                   first: 0x14,
+                   second: 0xF4,
+               }));
+    assert_eq!(SingleByteCodepageBuilder::from_dialect(&CP850_WINGDINGS).alias('¶', 0x14).build().unwrap().encode('¶'),
+               Some(0x14));
+}
+
+#[test]
+fn ambiguous_alias() {
+    assert_eq!(SingleByteCodepageBuilder::new().alias('A', 0x80).build(),
+               Err(BuildError::Ambiguous {
+                   unicode: 'A',
+                   first: 0x41,
+                   second: 0x80,
+               }));
+    assert_eq!(SingleByteCodepageBuilder::new().alias('Ā', 0x80).map(0x90, 'Ā').build(),
+               Err(BuildError::Ambiguous {
+                   unicode: 'Ā',
+                   first: 0x90,
+                   second: 0x80,
+               }));
+}
+
+#[test]
+fn bad_overlap() {
+    assert_eq!(SingleByteCodepageBuilder::new().map(0x41, 'Ā').overlap(0x20..=0x7E).build(),
+               Err(BuildError::Overlap { cp437: 0x41 }));
+    assert_eq!(SingleByteCodepageBuilder::new().overlap(0x70..=0x80).build(), Err(BuildError::Overlap { cp437: 0x80 }));
+    assert_eq!(SingleByteCodepageBuilder::new().undefine(0x41).overlap(0x41..=0x41).build(), Err(BuildError::Overlap { cp437: 0x41 }));
+    assert_eq!(BuildError::Overlap { cp437: 0x80 }.to_string(), "0x80 declared overlapping, but must be ASCII, defined, and decode to U+0080");
+}
+
+#[test]
+fn undefined_alias() {
+    assert_eq!(SingleByteCodepageBuilder::new().undefine(0x81).alias('x', 0x81).build(),
+               Err(BuildError::Undefined {
+                   unicode: 'x',
+                   cp437: 0x81,
+               }));
+    assert_eq!(SingleByteCodepageBuilder::new().alias('x', 0x81).undefine(0x81).build(),
+               Err(BuildError::Undefined {
+                   unicode: 'x',
+                   cp437: 0x81,
+               }));
+    assert_eq!(BuildError::Undefined { unicode: 'x', cp437: 0x81 }.to_string(), "U+0078 aliased to undefined 0x81");
+}
@@ -0,0 +1,38 @@
+use codepage_437::{CP437_CONTROL, CP850_CONTROL, CP866_WINGDINGS, BorrowFromCodepage, BorrowFromCp437, FromCodepage, FromCp437, IntoCodepage, IntoCp437,
+                   ToCodepage, ToCp437, SingleByteCodepage, Cp437Dialect, ReplacementPolicy};
+use std::borrow::Cow;
+
+
+#[test]
+fn alias() {
+    let dialect: &Cp437Dialect = &CP437_CONTROL;
+    let codepage: &SingleByteCodepage = dialect;
+
+    assert_eq!(codepage, &CP437_CONTROL);
+}
+
+#[test]
+fn decode_same_as_cp437() {
+    for dialect in &[&CP437_CONTROL, &CP850_CONTROL, &CP866_WINGDINGS] {
+        let all: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(String::from_codepage(all.clone(), dialect), String::from_cp437(all.clone(), dialect));
+        assert_eq!(Cow::borrow_from_codepage(&all, dialect), Cow::<str>::borrow_from_cp437(&all, dialect));
+        assert_eq!(String::borrow_from_codepage(&all, dialect), String::borrow_from_cp437(&all, dialect));
+    }
+}
+
+#[test]
+fn encode_same_as_cp437() {
+    for dialect in &[&CP437_CONTROL, &CP850_CONTROL, &CP866_WINGDINGS] {
+        for unicode in &["Local news", "Øresund øl £5", "Жук ½", "Jurek je żurek w żupanie."] {
+            assert_eq!(unicode.to_codepage(dialect), unicode.to_cp437(dialect));
+            assert_eq!(unicode.to_string().into_codepage(dialect), unicode.to_string().into_cp437(dialect));
+
+            assert_eq!(unicode.to_codepage_lossy(dialect, ReplacementPolicy::Skip),
+                       unicode.to_cp437_lossy(dialect, ReplacementPolicy::Skip));
+            assert_eq!(unicode.to_string().into_codepage_lossy(dialect, ReplacementPolicy::Substitute(b'?')),
+                       unicode.to_string().into_cp437_lossy(dialect, ReplacementPolicy::Substitute(b'?')));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, CP850_CONTROL, SingleByteCodepageBuilder};
+use self::super::ALL_DIALECTS;
+
+
+#[test]
+fn same() {
+    for dialect in ALL_DIALECTS {
+        assert!(dialect.diff(dialect).is_empty());
+        assert_eq!(dialect.diff(dialect).to_string(), "");
+    }
+
+    let built = SingleByteCodepageBuilder::from_dialect(&CP437_WINGDINGS).build().unwrap();
+    assert_ne!(built, CP437_WINGDINGS);
+    assert!(built.diff(&CP437_WINGDINGS).is_empty());
+}
+
+#[test]
+fn symmetric() {
+    let diff = CP437_CONTROL.diff(&CP850_CONTROL);
+    let reverse = CP850_CONTROL.diff(&CP437_CONTROL);
+
+    assert_eq!(diff.decode.iter().map(|&(b, this, other)| (b, other, this)).collect::<Vec<_>>(), reverse.decode);
+    assert_eq!(diff.encode.iter().map(|&(c, this, other)| (c, other, this)).collect::<Vec<_>>(), reverse.encode);
+}
+
+#[test]
+fn complete() {
+    for dialect in &[&CP437_WINGDINGS, &CP850_CONTROL] {
+        let diff = CP437_CONTROL.diff(dialect);
+
+        for b in 0..=0xFF {
+            let differs = CP437_CONTROL.decode(b) != dialect.decode(b);
+            assert_eq!(diff.decode.iter().any(|&(d, _, _)| d == b), differs);
+        }
+        for (_, c) in CP437_CONTROL.mappings().chain(dialect.mappings()) {
+            let differs = CP437_CONTROL.encode(c) != dialect.encode(c);
+            assert_eq!(diff.encode.iter().any(|&(d, _, _)| d == c), differs);
+        }
+    }
+}
+
+#[test]
+fn remapped() {
+    let mut mapping = CP437_CONTROL.clone();
+    mapping.remap(0x41, 'Ź').remap(0x9E, '€');
+
+    let diff = CP437_CONTROL.diff(&mapping);
+    assert_eq!(diff.decode, [(0x41, 'A', 'Ź'), (0x9E, '₧', '€')]);
+    assert_eq!(diff.encode, [('Ź', None, Some(0x41)), ('€', Some(0xEE), Some(0x9E))]);
+    assert_eq!(diff.to_string(),
+               "byte  this        other\n\
+                0x41  U+0041 'A'  U+0179 'Ź'\n\
+                0x9E  U+20A7 '₧'  U+20AC '€'\n\
+                \n\
+                char        this  other\n\
+                U+0179 'Ź'  -     0x41\n\
+                U+20AC '€'  0xEE  0x9E\n");
+}
@@ -0,0 +1,69 @@
+use codepage_437::CP437_CONTROL;
+#[cfg(feature = "alloc")]
+use codepage_437::{CodepageError, IntoCodepage, ToCodepage};
+#[cfg(feature = "std")]
+use codepage_437::{CP850_CONTROL, Cp437Writer};
+#[cfg(feature = "std")]
+use std::io::Write;
+
+
+#[test]
+#[cfg(feature = "alloc")]
+fn multibyte_before() {
+    let unicode = "Café ░▒▓ żurek";
+    let error = CodepageError {
+        representable_up_to: 16,
+        char_index: 9,
+        unrepresentable: 'ż',
+    };
+
+    assert_eq!(unicode.to_codepage(&CP437_CONTROL), Err(error));
+    assert_eq!(unicode.to_string().into_codepage(&CP437_CONTROL).map_err(|e| e.codepage_error()), Err(error));
+    assert_eq!(CP437_CONTROL.encode_into(unicode, &mut vec![]), Err(error));
+    assert_eq!(CP437_CONTROL.encode_from_utf8(unicode, &mut [0; 32]), Err(error));
+    assert_eq!(&unicode[..error.valid_up_to()], "Café ░▒▓ ");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn multibyte_before_writer() {
+    let mut writer = Cp437Writer::new(vec![], &CP437_CONTROL);
+    writer.write_all("Café ░".as_bytes()).unwrap();
+
+    let error = writer.write_all("▒▓ żurek".as_bytes()).unwrap_err();
+    assert_eq!(error.get_ref().unwrap().downcast_ref::<CodepageError>(),
+               Some(&CodepageError {
+                   representable_up_to: 16,
+                   char_index: 9,
+                   unrepresentable: 'ż',
+               }));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn question_mark() {
+    fn encode(unicode: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(unicode.to_codepage(&CP850_CONTROL)?.into_owned())
+    }
+
+    assert_eq!(encode("Øresund").unwrap(), b"\x9Dresund");
+    assert_eq!(encode("Żurek").unwrap_err().to_string(), "character 'Ż' (U+017B) at index 0 not representable in codepage");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn unrepresentable_all() {
+    let unicode = "Zażółć gęślą jaźń";
+    let unrepresentable = [(2, 'ż'), (6, 'ł'), (8, 'ć'), (12, 'ę'), (14, 'ś'), (17, 'ą'), (22, 'ź'), (24, 'ń')];
+
+    assert_eq!(CP437_CONTROL.unrepresentable(unicode).collect::<Vec<_>>(), unrepresentable);
+    assert!(CP437_CONTROL.unrepresentable(unicode).all(|(i, c)| unicode[i..].starts_with(c)));
+    assert_eq!(unicode.to_string().into_codepage(&CP437_CONTROL).unwrap_err().unrepresentable(), unrepresentable);
+}
+
+#[test]
+fn unrepresentable_none() {
+    assert_eq!(CP437_CONTROL.unrepresentable("").next(), None);
+    assert_eq!(CP437_CONTROL.unrepresentable("Local news").next(), None);
+    assert_eq!(CP437_CONTROL.unrepresentable("Café ░▒▓").next(), None);
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "alloc")]
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, CP850_WINGDINGS, CP857_CONTROL, SingleByteCodepage, SingleByteCodepageBuilder};
+use self::super::ALL_DIALECTS;
+#[cfg(feature = "alloc")]
+use std::char;
+
+
+#[cfg(feature = "alloc")]
+fn aliases_brute_force(dialect: &SingleByteCodepage) -> Vec<(char, u8)> {
+    (0..=0x10FFFF)
+        .filter_map(char::from_u32)
+        .filter_map(|c| dialect.encode(c).map(|b| (c, b)))
+        .filter(|&(c, b)| dialect.decode(b) != c)
+        .collect()
+}
+
+
+#[test]
+fn mappings() {
+    for dialect in ALL_DIALECTS {
+        let mappings: Vec<_> = dialect.mappings().collect();
+        assert_eq!(mappings.len(), 256);
+        for (i, &(b, c)) in mappings.iter().enumerate() {
+            assert_eq!(b as usize, i);
+            assert_eq!(dialect.decode(b), c);
+        }
+
+        assert_eq!(dialect.mappings().rev().collect::<Vec<_>>(), mappings.into_iter().rev().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn aliases() {
+    for dialect in &[&CP437_CONTROL, &CP437_WINGDINGS, &CP850_WINGDINGS, &CP857_CONTROL] {
+        assert_eq!(dialect.aliases(), aliases_brute_force(dialect));
+    }
+
+    assert!(CP437_CONTROL.aliases().contains(&('\u{1D719}', 0xED)));
+    assert!(!CP857_CONTROL.aliases().iter().any(|&(c, _)| c == 'Õ'));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn aliases_remapped() {
+    let mut mapping = CP437_WINGDINGS.clone();
+    mapping.remap(0xFB, '✓').remap(0xFB, '√').remap(0x41, '✓').remap(0xFB, 'Ź');
+
+    assert_eq!(mapping.aliases(), aliases_brute_force(&mapping));
+    assert!(mapping.aliases().contains(&('√', 0xFB)));
+    assert!(mapping.aliases().contains(&('A', 0x41)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn aliases_built() {
+    let dialect = SingleByteCodepageBuilder::from_dialect(&CP437_WINGDINGS).map(0xFB, '✓').alias('√', 0xFB).alias('‧', 0xF9).build().unwrap();
+
+    assert_eq!(dialect.aliases(), aliases_brute_force(&dialect));
+    assert!(dialect.aliases().contains(&('‧', 0xF9)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn remaps() {
+    assert_eq!(CP437_WINGDINGS.remaps(), []);
+
+    let mut mapping = CP437_WINGDINGS.clone();
+    mapping.remap(0xFB, '✓').remap(0x41, 'Ź');
+    assert_eq!(mapping.remaps(), [(0xFB, '√', '✓'), (0x41, 'A', 'Ź')]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn names() {
+    for dialect in ALL_DIALECTS {
+        for (b, c) in dialect.mappings().filter(|&(b, _)| b >= 0x80 && dialect.defined(b)) {
+            assert!(dialect.name(c).is_some(), "0x{:02X} in {:?}", b, dialect);
+        }
+        for (c, _) in dialect.aliases() {
+            assert!(dialect.name(c).is_some(), "{:?} in {:?}", c, dialect);
+        }
+    }
+
+    assert_eq!(CP437_WINGDINGS.name('☺'), Some("WHITE SMILING FACE"));
+    assert_eq!(CP437_CONTROL.name('☺'), None);
+    assert_eq!(CP437_CONTROL.name('\u{1}'), None);
+}
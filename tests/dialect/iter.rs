@@ -0,0 +1,63 @@
+use codepage_437::{CP437_CONTROL, DecodeCodepage};
+#[cfg(feature = "alloc")]
+use codepage_437::{CP437_WINGDINGS, CodepageError, BorrowFromCodepage, EncodeCodepage, ToCodepage};
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_same_as_borrow() {
+    let cp437: Vec<u8> = (0..=0xFF).collect();
+
+    for dialect in &[&CP437_CONTROL, &CP437_WINGDINGS] {
+        let whole = Cow::<str>::borrow_from_codepage(&cp437[..], dialect);
+        assert_eq!(dialect.decode_iter(cp437.iter().cloned()).collect::<String>(), whole);
+        assert_eq!(cp437.iter().cloned().decode_codepage(dialect).collect::<String>(), whole);
+        assert_eq!(dialect.decode_iter(cp437.iter().cloned()).rev().collect::<String>(), whole.chars().rev().collect::<String>());
+    }
+}
+
+#[test]
+fn decode_size() {
+    assert_eq!(CP437_CONTROL.decode_iter(vec![0x9E, 0xAB, 0x20]).len(), 3);
+    assert_eq!(CP437_CONTROL.decode_iter(Some(0x9E)).size_hint(), (1, Some(1)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_same_as_to() {
+    let unicode = "Local news: ╔══╗ ░▒▓ ₧½ million\r\n";
+    let whole = unicode.to_codepage(&CP437_CONTROL).unwrap();
+
+    assert_eq!(CP437_CONTROL.encode_iter(unicode.chars()).collect::<Result<Vec<_>, _>>(), Ok(whole.to_vec()));
+    assert_eq!(unicode.chars().encode_codepage(&CP437_CONTROL).collect::<Result<Vec<_>, _>>(), Ok(whole.to_vec()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_unrepresentable() {
+    let unicode = "Café ░ żurek ż";
+    let errors: Vec<_> = CP437_CONTROL.encode_iter(unicode.chars()).filter_map(Result::err).collect();
+
+    assert_eq!(errors,
+               [CodepageError {
+                    representable_up_to: 10,
+                    char_index: 7,
+                    unrepresentable: 'ż',
+                },
+                CodepageError {
+                    representable_up_to: 17,
+                    char_index: 13,
+                    unrepresentable: 'ż',
+                }]);
+    assert_eq!(Err(errors[0]), unicode.to_codepage(&CP437_CONTROL));
+}
+
+#[test]
+fn lazy() {
+    let mut decoded = 0;
+    let first_nonascii = (0..=0xFFu8).inspect(|_| decoded += 1).decode_codepage(&CP437_CONTROL).find(|c| !c.is_ascii());
+    assert_eq!(first_nonascii, Some('Ç'));
+    assert_eq!(decoded, 0x81);
+}
@@ -0,0 +1,142 @@
+use codepage_437::{CP437_CONTROL, CP1252, ISO_8859_3, SingleByteCodepage, MappingError, FromCodepage, ToCodepage};
+use std::borrow::Cow;
+use std::fs::File;
+
+
+fn error(mapping: &str) -> MappingError {
+    SingleByteCodepage::from_mapping_reader(mapping.as_bytes()).unwrap_err()
+}
+
+
+#[test]
+fn unicode_consortium() {
+    let dialect = SingleByteCodepage::from_mapping_reader(File::open("test-data/mappings/CP437.TXT").unwrap()).unwrap();
+
+    for b in 0..=255 {
+        assert_eq!(dialect.decode(b), CP437_CONTROL.decode(b));
+        assert_eq!(dialect.encode(CP437_CONTROL.decode(b)), Some(b));
+        assert_eq!(dialect.overlap_cp437(b), CP437_CONTROL.overlap_cp437(b));
+    }
+
+    assert_eq!(dialect.encode('ź'), None);
+    assert_eq!("Local news".to_codepage(&dialect), Ok(Cow::Borrowed(&b"Local news"[..])));
+}
+
+#[test]
+fn dialect_specs() {
+    let dialect = SingleByteCodepage::from_mapping_reader(File::open("dialect-specs/cp437_control/values.tsv").unwrap()).unwrap();
+
+    for b in 0..=255 {
+        assert_eq!(dialect.decode(b), CP437_CONTROL.decode(b));
+        assert_eq!(dialect.encode(CP437_CONTROL.decode(b)), Some(b));
+    }
+}
+
+#[test]
+fn undefined() {
+    let dialect = SingleByteCodepage::from_mapping_reader(File::open("test-data/mappings/CP1252.TXT").unwrap()).unwrap();
+
+    assert_eq!(String::from_codepage(vec![0x80, 0x81, 0x9F, 0xE9], &dialect), "€\u{81}Ÿé");
+    assert_eq!(dialect.encode('\u{81}'), None);
+    assert_eq!(dialect.encode('Ÿ'), Some(0x9F));
+    assert_eq!(dialect.encode('A'), Some(0x41));
+    assert!(dialect.overlap_unicode('A'));
+
+    assert!(!dialect.defined(0x81));
+    assert!(dialect.diff(&CP1252).is_empty());
+}
+
+#[test]
+fn undefined_dialect_specs() {
+    let dialect = SingleByteCodepage::from_mapping_reader(File::open("dialect-specs/iso_8859_3/values.tsv").unwrap()).unwrap();
+
+    for b in 0..=0xFF {
+        assert_eq!(dialect.try_decode(b), ISO_8859_3.try_decode(b));
+    }
+}
+
+#[test]
+fn unlisted_ascii() {
+    let dialect = SingleByteCodepage::from_mapping_reader("0x41\t0x0391\t#GREEK CAPITAL LETTER ALPHA\n".as_bytes()).unwrap();
+
+    assert_eq!(dialect.decode(0x41), 'Α');
+    assert_eq!(dialect.decode(0x42), 'B');
+    assert_eq!(dialect.encode('Α'), Some(0x41));
+    assert_eq!(dialect.encode('B'), Some(0x42));
+    assert_eq!(dialect.encode('A'), None);
+    assert!(!dialect.overlap_cp437(0x41));
+    assert!(dialect.overlap_cp437(0x42));
+}
+
+#[test]
+fn last_encode_wins() {
+    let dialect = SingleByteCodepage::from_mapping_reader("0x01\t0x00A7\n0x15\t0x00A7\n".as_bytes()).unwrap();
+
+    assert_eq!(dialect.decode(0x01), '§');
+    assert_eq!(dialect.decode(0x15), '§');
+    assert_eq!(dialect.encode('§'), Some(0x15));
+}
+
+#[test]
+fn bad_prefix() {
+    match error("0x41\t0x0041\n41\t0x0041\n") {
+        MappingError::BadPrefix { line: 2 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("0x41\tU+0041\n") {
+        MappingError::BadPrefix { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn out_of_range() {
+    match error("0x100\t0x0041\n") {
+        MappingError::OutOfRange { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("#\n0x41\t0x110000\n") {
+        MappingError::OutOfRange { line: 2 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("0x41\t0xD800\n") {
+        MappingError::OutOfRange { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("0x41\t0x1000000000\n") {
+        MappingError::OutOfRange { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn duplicate() {
+    match error("cp\tUnicode\tComment\n0x41\t0x0041\tA\n0x42\t0x0042\tB\n0x41\t0x0391\tAlpha\n") {
+        MappingError::Duplicate { line: 4, first: 2 } => {}
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn malformed() {
+    match error("0x41\n") {
+        MappingError::Malformed { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("0x4G\t0x0041\n") {
+        MappingError::Malformed { line: 1 } => {}
+        e => panic!("{:?}", e),
+    }
+    match error("0x41\t0x0041\ncp\tUnicode\n") {
+        MappingError::BadPrefix { line: 2 } => {}
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn io() {
+    match SingleByteCodepage::from_mapping_reader(&b"0x41\t0x0041\t#\xFF\n"[..]).unwrap_err() {
+        MappingError::Io(_) => {}
+        e => panic!("{:?}", e),
+    }
+}
@@ -1 +1,79 @@
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, CP737_CONTROL, CP737_WINGDINGS, CP850_CONTROL, CP850_WINGDINGS, CP852_CONTROL, CP852_WINGDINGS,
+                   CP857_CONTROL, CP857_WINGDINGS, CP860_CONTROL, CP860_WINGDINGS, CP861_CONTROL, CP861_WINGDINGS, CP862_CONTROL, CP862_WINGDINGS,
+                   CP863_CONTROL, CP863_WINGDINGS, CP865_CONTROL, CP865_WINGDINGS, CP866_CONTROL, CP866_WINGDINGS, CP1250, CP1251, CP1252, CP1253, CP1254, CP1255, CP1256, CP1257, CP1258,
+                   ISO_8859_1, ISO_8859_2, ISO_8859_3, ISO_8859_4, ISO_8859_5, ISO_8859_6, ISO_8859_7, ISO_8859_8, ISO_8859_9, ISO_8859_10, ISO_8859_11,
+                   ISO_8859_13, ISO_8859_14, ISO_8859_15, ISO_8859_16, PETSCII_UNSHIFTED, PETSCII_SHIFTED, ATASCII_NORMAL,
+                   CP037, CP500, CP1047, SingleByteCodepage};
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+mod codepage;
+#[cfg(feature = "alloc")]
+mod diff;
+mod error;
+mod introspect;
+mod iter;
+#[cfg(feature = "std")]
+mod mapping;
+mod overlap;
+#[cfg(feature = "alloc")]
 mod remap;
+#[cfg(feature = "alloc")]
+mod round_trip;
+mod slice;
+mod undefined;
+
+
+const ALL_DIALECTS: &[&SingleByteCodepage] = &[&CP437_CONTROL,
+                                               &CP437_WINGDINGS,
+                                               &CP737_CONTROL,
+                                               &CP737_WINGDINGS,
+                                               &CP850_CONTROL,
+                                               &CP850_WINGDINGS,
+                                               &CP852_CONTROL,
+                                               &CP852_WINGDINGS,
+                                               &CP857_CONTROL,
+                                               &CP857_WINGDINGS,
+                                               &CP860_CONTROL,
+                                               &CP860_WINGDINGS,
+                                               &CP861_CONTROL,
+                                               &CP861_WINGDINGS,
+                                               &CP862_CONTROL,
+                                               &CP862_WINGDINGS,
+                                               &CP863_CONTROL,
+                                               &CP863_WINGDINGS,
+                                               &CP865_CONTROL,
+                                               &CP865_WINGDINGS,
+                                               &CP866_CONTROL,
+                                               &CP866_WINGDINGS,
+                                               &CP1250,
+                                               &CP1251,
+                                               &CP1252,
+                                               &CP1253,
+                                               &CP1254,
+                                               &CP1255,
+                                               &CP1256,
+                                               &CP1257,
+                                               &CP1258,
+                                               &ISO_8859_1,
+                                               &ISO_8859_2,
+                                               &ISO_8859_3,
+                                               &ISO_8859_4,
+                                               &ISO_8859_5,
+                                               &ISO_8859_6,
+                                               &ISO_8859_7,
+                                               &ISO_8859_8,
+                                               &ISO_8859_9,
+                                               &ISO_8859_10,
+                                               &ISO_8859_11,
+                                               &ISO_8859_13,
+                                               &ISO_8859_14,
+                                               &ISO_8859_15,
+                                               &ISO_8859_16,
+                                               &PETSCII_UNSHIFTED,
+                                               &PETSCII_SHIFTED,
+                                               &ATASCII_NORMAL,
+                                               &CP037,
+                                               &CP500,
+                                               &CP1047];
@@ -0,0 +1,65 @@
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS};
+#[cfg(feature = "alloc")]
+use codepage_437::{BorrowFromCodepage, FromCodepage, ToCodepage};
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+#[test]
+fn prefix_ascii() {
+    for len in 0..40 {
+        let mut data = vec![b'a'; len];
+        assert_eq!(CP437_CONTROL.overlap_cp437_prefix(&data), len);
+
+        data.push(0xB0);
+        data.extend_from_slice(b"more ASCII after");
+        assert_eq!(CP437_CONTROL.overlap_cp437_prefix(&data), len);
+        if len != 0 {
+            assert_eq!(CP437_CONTROL.overlap_cp437_prefix(&data[1..]), len - 1);
+        }
+    }
+}
+
+#[test]
+fn prefix_partial() {
+    // 0x01 is ☺ in wingdings
+    for len in 0..40 {
+        let mut data = vec![b'a'; len];
+        data.push(0x01);
+        data.extend_from_slice(b"more ASCII after");
+
+        assert_eq!(CP437_CONTROL.overlap_cp437_prefix(&data), data.len());
+        assert_eq!(CP437_WINGDINGS.overlap_cp437_prefix(&data), len);
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn prefix_remapped() {
+    let mut dialect = CP437_CONTROL.clone();
+    dialect.remap(b'q', 'ǫ');
+
+    assert_eq!(dialect.overlap_cp437_prefix(b"the quick brown fox"), 4);
+    assert_eq!(dialect.overlap_unicode_prefix("the ǫuick brown fox"), 4);
+    assert_eq!(dialect.overlap_unicode_prefix("the quick brown fox"), 4);
+}
+
+#[test]
+fn prefix_unicode() {
+    assert_eq!(CP437_CONTROL.overlap_unicode_prefix("Local news ₧½ million"), 11);
+    assert_eq!(CP437_CONTROL.overlap_unicode_prefix("Local news"), 10);
+    assert_eq!(CP437_CONTROL.overlap_unicode_prefix(""), 0);
+    assert_eq!(CP437_WINGDINGS.overlap_unicode_prefix("Local\tnews"), 5);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn after_prefix() {
+    let unicode = "A long line of plain text before any box drawing: ╔══╗ ║ ░▒▓ ║ and back to ASCII.";
+    let cp437 = unicode.to_codepage(&CP437_CONTROL).unwrap();
+
+    assert_eq!(cp437[..49], unicode.as_bytes()[..49]);
+    assert_eq!(cp437[50..54], [0xC9, 0xCD, 0xCD, 0xBB]);
+    assert_eq!(String::from_codepage(cp437.clone().into_owned(), &CP437_CONTROL), unicode);
+    assert_eq!(Cow::<str>::borrow_from_codepage(&cp437[..], &CP437_CONTROL), unicode);
+}
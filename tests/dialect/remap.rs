@@ -1,4 +1,6 @@
-use codepage_437::CP437_WINGDINGS;
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, ToCodepage};
+use std::borrow::Cow;
+use std::char;
 
 
 #[test]
@@ -55,3 +57,35 @@ fn double() {
     assert_eq!(mapping.decode(0x41), 'Ź');
     assert_eq!(mapping.decode(0xFB), '✓');
 }
+
+#[test]
+fn overlap() {
+    let mut mapping = CP437_CONTROL.clone();
+    mapping.remap(0x41, 'Ź');
+    mapping.remap(0x80, 'B');
+
+    assert!(!mapping.overlap_cp437(0x41));
+    assert!(!mapping.overlap_unicode('B'));
+    assert!(mapping.overlap_cp437(0x43));
+    assert!(mapping.overlap_unicode('C'));
+
+    assert_eq!("CBŹ".to_codepage(&mapping), Ok(Cow::Owned(vec![0x43, 0x80, 0x41])));
+    assert_eq!("CD".to_codepage(&mapping), Ok(Cow::Borrowed(&b"CD"[..])));
+}
+
+#[test]
+fn many() {
+    let mut mapping = CP437_CONTROL.clone();
+    for b in 0x80..=0xFF {
+        mapping.remap(b, char::from_u32(0xF700 + b as u32).unwrap());
+    }
+    mapping.remap(0x9B, '¢');
+
+    for b in 0x80..=0xFF {
+        assert_eq!(mapping.encode(mapping.decode(b)), Some(b));
+    }
+    assert_eq!(mapping.encode('\u{F79B}'), Some(0x9B));
+    assert_eq!(mapping.encode('Ç'), Some(0x80));
+    assert_eq!(mapping.encode('A'), Some(0x41));
+    assert!(mapping.overlap_unicode('A'));
+}
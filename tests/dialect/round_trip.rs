@@ -0,0 +1,55 @@
+use codepage_437::{CP437_CONTROL, CP850_WINGDINGS, CP857_CONTROL, FromCp437, ToCp437};
+use self::super::ALL_DIALECTS;
+use std::char;
+
+
+#[test]
+fn every_byte() {
+    for dialect in ALL_DIALECTS {
+        let raw = dialect.round_trip();
+        for b in 0..=0xFF {
+            assert!(raw.round_trips(b), "0x{:02X} in {:?}", b, dialect);
+            assert_eq!(raw.encode(raw.decode(b)), Some(b));
+        }
+    }
+}
+
+#[test]
+fn every_buffer() {
+    let data: Vec<u8> = (0..=0xFF).chain((0..=0xFF).rev()).collect();
+    for dialect in ALL_DIALECTS {
+        let raw = dialect.round_trip();
+        assert_eq!(String::from_cp437(data.clone(), &raw).to_cp437(&raw), Ok(data[..].into()));
+    }
+}
+
+#[test]
+fn unchanged() {
+    for dialect in ALL_DIALECTS {
+        let raw = dialect.round_trip();
+        for b in (0..=0xFF).filter(|&b| dialect.round_trips(b)) {
+            assert_eq!(raw.decode(b), dialect.decode(b));
+        }
+        for c in (0..0xF700).chain(0xF800..0x10000).filter_map(char::from_u32) {
+            assert_eq!(raw.encode(c), dialect.encode(c));
+        }
+    }
+
+    assert_eq!(CP437_CONTROL.round_trip(), CP437_CONTROL);
+}
+
+#[test]
+fn remapped() {
+    let raw = CP850_WINGDINGS.round_trip();
+    assert_eq!(CP850_WINGDINGS.decode(0x14), CP850_WINGDINGS.decode(0xF4));
+    assert_eq!(raw.decode(0x14), '\u{F714}');
+    assert_eq!(raw.decode(0xF4), CP850_WINGDINGS.decode(0xF4));
+    assert_eq!(raw.encode('\u{F714}'), Some(0x14));
+    assert_eq!(raw.encode('\u{F7F4}'), None);
+
+    // Undefined 0xD5 decodes to U+00D5, which is at 0xE5
+    let raw = CP857_CONTROL.round_trip();
+    assert_eq!(raw.decode(0xD5), '\u{F7D5}');
+    assert_eq!(raw.decode(0xE5), 'Õ');
+    assert_eq!(raw.encode('\u{F7D5}'), Some(0xD5));
+}
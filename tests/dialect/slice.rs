@@ -0,0 +1,139 @@
+use codepage_437::{CP437_CONTROL, CP437_WINGDINGS, CodepageError};
+#[cfg(feature = "alloc")]
+use codepage_437::{CP866_CONTROL, BorrowFromCodepage, ToCodepage};
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_same_as_borrow() {
+    let cp437: Vec<u8> = (0..=0xFF).collect();
+    let mut buf = [0; 256 * 3];
+
+    for dialect in &[&CP437_CONTROL, &CP437_WINGDINGS, &CP866_CONTROL] {
+        assert_eq!(&*dialect.decode_to_slice(&cp437, &mut buf).unwrap(), Cow::<str>::borrow_from_codepage(&cp437[..], dialect));
+    }
+}
+
+#[test]
+fn decode_exact_buffer() {
+    let mut buf = [0; 7];
+    assert_eq!(CP437_CONTROL.decode_to_slice(&[0x41, 0x9E, 0xAB], &mut buf[..6]).map(|s| &*s), Some("A₧½"));
+    assert_eq!(buf[6], 0);
+}
+
+#[test]
+fn decode_short_buffer() {
+    let mut buf = [0; 5];
+    assert_eq!(CP437_CONTROL.decode_to_slice(&[0x41, 0x9E, 0xAB], &mut buf), None);
+    assert_eq!(CP437_CONTROL.decode_to_slice(&[0x41], &mut []), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_same_as_to() {
+    let unicode = "Local news: ╔══╗ ░▒▓ ₧½ million\r\n";
+    let mut buf = [0; 64];
+
+    assert_eq!(CP437_CONTROL.encode_to_slice(unicode, &mut buf).map(|cp437| &cp437.unwrap()[..]),
+               unicode.to_codepage(&CP437_CONTROL).as_ref().map(|cp437| &cp437[..]).map_err(|&e| e));
+}
+
+#[test]
+fn encode_exact_buffer() {
+    let mut buf = [0; 4];
+    assert_eq!(CP437_CONTROL.encode_to_slice("A₧½", &mut buf[..3]), Ok(Some(&mut [0x41, 0x9E, 0xAB][..])));
+    assert_eq!(buf[3], 0);
+}
+
+#[test]
+fn encode_unrepresentable() {
+    let mut buf = [0; 16];
+    assert_eq!(CP437_CONTROL.encode_to_slice("Jurek je żurek", &mut buf), Err(CodepageError { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' }));
+    assert_eq!(CP437_WINGDINGS.encode_to_slice("Jurek\tje", &mut buf), Err(CodepageError { representable_up_to: 5, char_index: 5, unrepresentable: '\t' }));
+}
+
+#[test]
+fn encode_short_buffer() {
+    let mut buf = [0; 2];
+    assert_eq!(CP437_CONTROL.encode_to_slice("A₧½", &mut buf), Ok(None));
+    assert_eq!(CP437_CONTROL.encode_to_slice("A₧ż", &mut buf), Ok(None));
+    assert_eq!(CP437_CONTROL.encode_to_slice("Aż", &mut buf), Err(CodepageError { representable_up_to: 1, char_index: 1, unrepresentable: 'ż' }));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_chunked() {
+    let cp437: Vec<u8> = (0..=0xFF).cycle().take(1000).collect();
+    let whole = Cow::<str>::borrow_from_codepage(&cp437[..], &CP437_CONTROL);
+
+    for size in 3..20 {
+        let mut buf = vec![0; size];
+        let mut unicode = String::new();
+        let mut cp437 = &cp437[..];
+        while !cp437.is_empty() {
+            let (read, written) = CP437_CONTROL.decode_to_utf8(cp437, &mut buf);
+            assert!(read != 0);
+            unicode.push_str(std::str::from_utf8(&buf[..written]).unwrap());
+            cp437 = &cp437[read..];
+        }
+        assert_eq!(unicode, whole);
+    }
+}
+
+#[test]
+fn decode_no_room() {
+    let mut buf = [0; 2];
+    assert_eq!(CP437_CONTROL.decode_to_utf8(&[0x9E], &mut buf), (0, 0));
+    assert_eq!(CP437_CONTROL.decode_to_utf8(&[0x41, 0x42, 0x43], &mut buf), (2, 2));
+    assert_eq!(CP437_CONTROL.decode_to_utf8(&[], &mut buf), (0, 0));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_into_appends() {
+    let mut unicode = "Local news: ".to_string();
+    CP437_CONTROL.decode_into(&[0x9E, 0xAB, 0x20, 0x6D, 0x69, 0x6C, 0x6C, 0x69, 0x6F, 0x6E], &mut unicode);
+    assert_eq!(unicode, "Local news: ₧½ million");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_chunked() {
+    let unicode = "Local news: ╔══╗ ░▒▓ ₧½ million\r\n".repeat(20);
+    let whole = unicode.to_codepage(&CP437_CONTROL).unwrap();
+
+    for size in 1..20 {
+        let mut buf = vec![0; size];
+        let mut cp437 = vec![];
+        let mut unicode = &unicode[..];
+        while !unicode.is_empty() {
+            let (read, written) = CP437_CONTROL.encode_from_utf8(unicode, &mut buf).unwrap();
+            assert!(read != 0);
+            cp437.extend_from_slice(&buf[..written]);
+            unicode = &unicode[read..];
+        }
+        assert_eq!(cp437, &whole[..]);
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_partial() {
+    let mut buf = [0; 16];
+    assert_eq!(CP437_CONTROL.encode_from_utf8("Jurek je żurek", &mut buf), Err(CodepageError { representable_up_to: 9, char_index: 9, unrepresentable: 'ż' }));
+    assert_eq!(buf[..9], b"Jurek je "[..]);
+
+    let mut cp437 = b"Jurek: ".to_vec();
+    assert_eq!(CP437_CONTROL.encode_into("╔═ żurek", &mut cp437), Err(CodepageError { representable_up_to: 7, char_index: 3, unrepresentable: 'ż' }));
+    assert_eq!(cp437, b"Jurek: \xC9\xCD ");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_into_appends() {
+    let mut cp437 = b"Local news: ".to_vec();
+    assert_eq!(CP437_CONTROL.encode_into("₧½ million", &mut cp437), Ok(()));
+    assert_eq!(cp437, b"Local news: \x9E\xAB million");
+}
@@ -0,0 +1,135 @@
+#[cfg(feature = "alloc")]
+use codepage_437::{CP437_CONTROL, CP857_CONTROL, CP857_WINGDINGS, CP1252, CP1253, ISO_8859_1, SingleByteCodepageBuilder, DecodeError, BorrowFromCp437, FromCp437};
+use self::super::ALL_DIALECTS;
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+#[test]
+fn try_decode() {
+    for dialect in ALL_DIALECTS {
+        for b in 0..=0xFF {
+            if dialect.defined(b) {
+                assert_eq!(dialect.try_decode(b), Some(dialect.decode(b)));
+            } else {
+                assert_eq!(dialect.try_decode(b), None);
+                assert_eq!(dialect.decode(b), b as char);
+                assert_ne!(dialect.encode(b as char), Some(b));
+                assert!(!dialect.overlap_cp437(b));
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn fully_defined() {
+    for &dialect in &[&CP437_CONTROL, &ISO_8859_1] {
+        let data: Vec<u8> = (0..=0xFF).collect();
+        assert_eq!(String::try_from_cp437(data.clone(), dialect), Ok(String::from_cp437(data.clone(), dialect)));
+        assert_eq!(String::from_cp437_lossy(data.clone(), dialect), String::from_cp437(data, dialect));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn strict() {
+    let data = [0x43, 0x61, 0x66, 0xE9, 0x20, 0x80, 0x90, 0x81];
+    let error = DecodeError {
+        defined_up_to: 6,
+        undefined: 0x90,
+    };
+
+    assert_eq!(String::try_from_cp437(data.to_vec(), &CP1252), Err(error));
+    assert_eq!(String::try_from_cp437(data.to_vec().into_boxed_slice(), &CP1252), Err(error));
+    assert_eq!(String::try_from_cp437(Cow::Borrowed(&data[..]), &CP1252), Err(error));
+    assert_eq!(String::try_from_cp437(data, &CP1252), Err(error));
+    assert_eq!(String::try_borrow_from_cp437(&data[..], &CP1252), Err(error));
+    assert_eq!(Cow::try_borrow_from_cp437(&data[..], &CP1252), Err(error));
+    assert_eq!(error.valid_up_to(), 6);
+
+    assert_eq!(String::try_from_cp437(data[..6].to_vec(), &CP1252), Ok("Café €".to_string()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn lossy() {
+    let data = [0x43, 0x61, 0x66, 0xE9, 0x20, 0x80, 0x90, 0x81];
+    let unicode = "Café €\u{FFFD}\u{FFFD}";
+
+    assert_eq!(String::from_cp437_lossy(data.to_vec(), &CP1252), unicode);
+    assert_eq!(String::from_cp437_lossy(data.to_vec().into_boxed_slice(), &CP1252), unicode);
+    assert_eq!(String::from_cp437_lossy(Cow::Borrowed(&data[..]), &CP1252), unicode);
+    assert_eq!(String::from_cp437_lossy(data, &CP1252), unicode);
+    assert_eq!(String::borrow_from_cp437_lossy(&data[..], &CP1252), unicode);
+    assert_eq!(Cow::borrow_from_cp437_lossy(&data[..], &CP1252), unicode);
+
+    assert_eq!(String::from_cp437(data.to_vec(), &CP1252), "Café €\u{90}\u{81}");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn cp857() {
+    for &dialect in &[&CP857_CONTROL, &CP857_WINGDINGS] {
+        for &(b, c, canonical) in &[(0xD5, 'Õ', 0xE5), (0xE7, 'ç', 0x87), (0xF2, 'ò', 0x95)] {
+            assert!(!dialect.defined(b));
+            assert_eq!(dialect.decode(b), c);
+            assert_eq!(dialect.encode(c), Some(canonical));
+            assert_eq!(String::try_from_cp437(vec![0x41, b], dialect),
+                       Err(DecodeError {
+                           defined_up_to: 1,
+                           undefined: b,
+                       }));
+            assert_eq!(String::from_cp437_lossy(vec![0x41, b], dialect), "A\u{FFFD}");
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn borrowed() {
+    assert!(matches!(Cow::try_borrow_from_cp437(&b"Local news"[..], &CP1253), Ok(Cow::Borrowed("Local news"))));
+    assert!(matches!(Cow::borrow_from_cp437_lossy(&b"Local news"[..], &CP1253), Cow::Borrowed("Local news")));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn builder() {
+    let dialect = SingleByteCodepageBuilder::from_dialect(&CP1252).build().unwrap();
+    assert_eq!(dialect.diff(&CP1252).decode, vec![]);
+    for b in 0..=0xFF {
+        assert_eq!(dialect.defined(b), CP1252.defined(b));
+    }
+
+    let dialect = SingleByteCodepageBuilder::from_dialect(&CP1252).undefine(0x41).map(0x81, '\u{2302}').build().unwrap();
+    assert_eq!(dialect.try_decode(0x41), None);
+    assert_eq!(dialect.encode('A'), None);
+    assert!(!dialect.overlap_cp437(0x41));
+    assert_eq!(dialect.try_decode(0x81), Some('⌂'));
+    assert_eq!(dialect.encode('⌂'), Some(0x81));
+    assert_eq!(String::try_from_cp437(vec![0x81, 0x41], &dialect),
+               Err(DecodeError {
+                   defined_up_to: 1,
+                   undefined: 0x41,
+               }));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn remap() {
+    let mut dialect = CP1252.clone();
+    dialect.remap(0x81, '⌂');
+    assert!(dialect.defined(0x81));
+    assert_eq!(String::try_from_cp437(vec![0x81], &dialect), Ok("⌂".to_string()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn display() {
+    assert_eq!(DecodeError {
+                       defined_up_to: 12,
+                       undefined: 0x9D,
+                   }
+                   .to_string(),
+               "codepoint 0x9D at index 12 undefined in codepage");
+}
@@ -0,0 +1,20 @@
+single_byte_tests!(cp737_control, CP737_CONTROL, "cp737_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp737_wingdings, CP737_WINGDINGS, "cp737_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp850_control, CP850_CONTROL, "cp850_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp850_wingdings, CP850_WINGDINGS, "cp850_wingdings", [], [0x14, 0x15], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp852_control, CP852_CONTROL, "cp852_control", [], [], "ĞğĦħĨĩĪīĲĳĴĵĶķ");
+single_byte_tests!(cp852_wingdings, CP852_WINGDINGS, "cp852_wingdings", [], [0x15], "ĞğĦħĨĩĪīĲĳĴĵĶķ");
+single_byte_tests!(cp857_control, CP857_CONTROL, "cp857_control", [0xD5, 0xE7, 0xF2], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp857_wingdings, CP857_WINGDINGS, "cp857_wingdings", [0xD5, 0xE7, 0xF2], [0x14, 0x15], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp860_control, CP860_CONTROL, "cp860_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp860_wingdings, CP860_WINGDINGS, "cp860_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp861_control, CP861_CONTROL, "cp861_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp861_wingdings, CP861_WINGDINGS, "cp861_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp862_control, CP862_CONTROL, "cp862_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp862_wingdings, CP862_WINGDINGS, "cp862_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp863_control, CP863_CONTROL, "cp863_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp863_wingdings, CP863_WINGDINGS, "cp863_wingdings", [], [0x14, 0x15], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp865_control, CP865_CONTROL, "cp865_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp865_wingdings, CP865_WINGDINGS, "cp865_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp866_control, CP866_CONTROL, "cp866_control", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
+single_byte_tests!(cp866_wingdings, CP866_WINGDINGS, "cp866_wingdings", [], [], "ĄĘĆŹŻŃŁąęćźżńł");
@@ -0,0 +1,43 @@
+#[cfg(feature = "alloc")]
+use codepage_437::{CP037, CP500, CP1047, BorrowFromCp437, ToCp437};
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+single_byte_tests!(cp037, CP037, "cp037", [], [], "☺", false);
+single_byte_tests!(cp500, CP500, "cp500", [], [], "☺", false);
+single_byte_tests!(cp1047, CP1047, "cp1047", [], [], "☺", false);
+
+
+#[test]
+#[cfg(feature = "alloc")]
+fn hello() {
+    let ebcdic = [0xC8, 0x85, 0x93, 0x93, 0x96, 0x6B, 0x40, 0xA6, 0x96, 0x99, 0x93, 0x84, 0x4B, 0x25];
+
+    for dialect in &[&CP037, &CP500, &CP1047] {
+        assert_eq!(String::borrow_from_cp437(&ebcdic, dialect), "Hello, world.\n");
+        assert_eq!("Hello, world.\n".to_cp437(dialect), Ok(Cow::Owned(ebcdic.to_vec())));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn not_overlapping() {
+    let ascii: Vec<u8> = (0x00..0x80).collect();
+    let unicode = String::from_utf8(ascii.clone()).unwrap();
+
+    for dialect in &[&CP037, &CP500, &CP1047] {
+        assert_eq!(dialect.overlap_cp437_prefix(&ascii), 0);
+        assert_eq!(dialect.overlap_unicode_prefix(&unicode), 0);
+        assert!(matches!(Cow::borrow_from_cp437(&[][..], dialect), Cow::Borrowed("")));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn cp1047_diff() {
+    let diff = CP1047.diff(&CP037);
+    let mut moved: Vec<_> = diff.decode.iter().map(|&(b, _, _)| b).collect();
+    moved.sort();
+    assert_eq!(moved, [0x5F, 0xAD, 0xB0, 0xBA, 0xBB, 0xBD]);
+}
@@ -0,0 +1,19 @@
+single_byte_tests!(iso_8859_1, ISO_8859_1, "iso_8859_1", []);
+single_byte_tests!(iso_8859_2, ISO_8859_2, "iso_8859_2", []);
+single_byte_tests!(iso_8859_3, ISO_8859_3, "iso_8859_3", [0xA5, 0xAE, 0xBE, 0xC3, 0xD0, 0xE3, 0xF0]);
+single_byte_tests!(iso_8859_4, ISO_8859_4, "iso_8859_4", []);
+single_byte_tests!(iso_8859_5, ISO_8859_5, "iso_8859_5", []);
+single_byte_tests!(iso_8859_6, ISO_8859_6, "iso_8859_6", [0xA1, 0xA2, 0xA3, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xAB, 0xAE, 0xAF, 0xB0, 0xB1, 0xB2, 0xB3,
+                                                          0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xBC, 0xBD, 0xBE, 0xC0, 0xDB, 0xDC, 0xDD, 0xDE, 0xDF,
+                                                          0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF]);
+single_byte_tests!(iso_8859_7, ISO_8859_7, "iso_8859_7", [0xAE, 0xD2, 0xFF]);
+single_byte_tests!(iso_8859_8, ISO_8859_8, "iso_8859_8", [0xA1, 0xBF, 0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD,
+                                                          0xCE, 0xCF, 0xD0, 0xD1, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xDB, 0xDC, 0xDD,
+                                                          0xDE, 0xFB, 0xFC, 0xFF]);
+single_byte_tests!(iso_8859_9, ISO_8859_9, "iso_8859_9", []);
+single_byte_tests!(iso_8859_10, ISO_8859_10, "iso_8859_10", []);
+single_byte_tests!(iso_8859_11, ISO_8859_11, "iso_8859_11", [0xDB, 0xDC, 0xDD, 0xDE, 0xFC, 0xFD, 0xFE, 0xFF]);
+single_byte_tests!(iso_8859_13, ISO_8859_13, "iso_8859_13", []);
+single_byte_tests!(iso_8859_14, ISO_8859_14, "iso_8859_14", []);
+single_byte_tests!(iso_8859_15, ISO_8859_15, "iso_8859_15", []);
+single_byte_tests!(iso_8859_16, ISO_8859_16, "iso_8859_16", []);
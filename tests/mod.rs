@@ -1,8 +1,126 @@
 extern crate codepage_437;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "smartstring")]
+extern crate smartstring;
+#[cfg(feature = "compact_str")]
+extern crate compact_str;
+
+
+/// Test a single-byte dialect against `test-data/$data/all.utf8`, everything but the specified undefined codepoints round-tripping,
+/// the specified duplicate codepoints encoding to another byte decoding to the same character, none of the unmapped sample encoding,
+/// and printable ASCII borrowing if `$borrowing`; for use from a directory one level down.
+macro_rules! single_byte_tests {
+    ($name:ident, $dialect:ident, $data:literal, [$($undefined:expr),*]) => {
+        single_byte_tests!($name, $dialect, $data, [$($undefined),*], [], "☺");
+    };
+    ($name:ident, $dialect:ident, $data:literal, [$($undefined:expr),*], [$($duplicate:expr),*], $unmapped:literal) => {
+        single_byte_tests!($name, $dialect, $data, [$($undefined),*], [$($duplicate),*], $unmapped, true);
+    };
+    ($name:ident, $dialect:ident, $data:literal, [$($undefined:expr),*], [$($duplicate:expr),*], $unmapped:literal, $borrowing:literal) => {
+        mod $name {
+            use codepage_437::$dialect;
+            #[cfg(feature = "alloc")]
+            use codepage_437::{BorrowFromCp437, FromCp437, IntoCp437, ToCp437};
+            #[cfg(feature = "std")]
+            use codepage_437::{Cp437Reader, Cp437Writer};
+            #[cfg(feature = "std")]
+            use std::io::{Read, Write};
+            #[cfg(feature = "alloc")]
+            use std::borrow::Cow;
+
+
+            const ALL_UTF8: &str = include_str!(concat!("../../test-data/", $data, "/all.utf8"));
+            const UNDEFINED: &[u8] = &[$($undefined),*];
+            const DUPLICATE: &[u8] = &[$($duplicate),*];
+
+            #[cfg(feature = "alloc")]
+            fn round_tripping() -> (Vec<u8>, String) {
+                ALL_UTF8.chars()
+                    .enumerate()
+                    .map(|(b, c)| (b as u8, c))
+                    .filter(|(b, _)| !UNDEFINED.contains(b) && !DUPLICATE.contains(b))
+                    .unzip()
+            }
+
+
+            #[test]
+            fn decode() {
+                assert_eq!(ALL_UTF8.chars().count(), 0x100);
+                for (b, c) in ALL_UTF8.chars().enumerate().map(|(b, c)| (b as u8, c)) {
+                    assert_eq!($dialect.decode(b), c);
+                    assert_eq!($dialect.defined(b), !UNDEFINED.contains(&b));
+
+                    if $dialect.overlap_cp437(b) {
+                        assert_eq!(b as char, c);
+                    }
+                }
+            }
+
+            #[test]
+            fn encode() {
+                for b in 0..=0xFF {
+                    if DUPLICATE.contains(&b) {
+                        let encoded = $dialect.encode($dialect.decode(b)).unwrap();
+                        assert_ne!(encoded, b);
+                        assert_eq!($dialect.decode(encoded), $dialect.decode(b));
+                    } else {
+                        assert_eq!($dialect.encode($dialect.decode(b)) == Some(b), $dialect.defined(b));
+                    }
+                }
+                for c in $unmapped.chars() {
+                    assert_eq!($dialect.encode(c), None);
+                }
+            }
+
+            #[test]
+            #[cfg(feature = "alloc")]
+            fn conversion() {
+                let (cp437, unicode) = round_tripping();
+
+                assert_eq!(String::from_cp437(cp437.clone(), &$dialect), unicode);
+                assert_eq!(String::borrow_from_cp437(&cp437, &$dialect), unicode);
+                assert_eq!(unicode.to_cp437(&$dialect), Ok(Cow::Borrowed(&cp437[..])));
+                assert_eq!(unicode.into_cp437(&$dialect), Ok(cp437));
+            }
+
+            #[test]
+            #[cfg(feature = "alloc")]
+            fn borrowing() {
+                let ascii: Vec<u8> = (0x20..0x7F).collect();
+                let unicode = String::from_utf8(ascii.clone()).unwrap();
+
+                assert_eq!(matches!(Cow::borrow_from_cp437(&ascii[..], &$dialect), Cow::Borrowed(_)), $borrowing);
+                assert_eq!(matches!(unicode.to_cp437(&$dialect), Ok(Cow::Borrowed(_))), $borrowing);
+            }
+
+            #[test]
+            #[cfg(feature = "std")]
+            fn stream() {
+                let all: Vec<u8> = (0..=0xFF).collect();
+                let mut unicode = String::new();
+                Cp437Reader::with_capacity(3, &all[..], &$dialect).read_to_string(&mut unicode).unwrap();
+                assert_eq!(unicode, ALL_UTF8);
+
+                let (cp437, unicode) = round_tripping();
+                let mut written = vec![];
+                Cp437Writer::new(&mut written, &$dialect).write_all(unicode.as_bytes()).unwrap();
+                assert_eq!(written, cp437);
+            }
+        }
+    }
+}
 
 mod cp437_wingdings;
 mod cp437_control;
+mod dbcs;
 mod dialect;
+mod dos;
+mod ebcdic;
+mod iso_8859;
+mod stateful;
+mod transliterate;
+mod windows;
 
 
 const ALL_CP437: &[u8] = include_bytes!("../test-data/all.cp437");
@@ -0,0 +1,62 @@
+use codepage_437::{ATASCII, ATASCII_NORMAL};
+#[cfg(feature = "alloc")]
+use codepage_437::CodepageState;
+#[cfg(feature = "std")]
+use codepage_437::Cp437Reader;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+
+#[test]
+fn decode() {
+    let mut decoder = ATASCII.decoder();
+    for b in 0..=0xFF {
+        let c = decoder.decode(b);
+        if b == 0x9B {
+            assert_eq!(c, Some('\n'));
+            assert!(!decoder.state().reverse);
+        } else {
+            assert_eq!(c, Some(ATASCII_NORMAL.decode(b & 0x7F)), "0x{:02X}", b);
+            assert_eq!(decoder.state().reverse, b >= 0x80);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode() {
+    let mut encoder = ATASCII.encoder();
+    let mut atascii = vec![];
+    for b in 0..0x80 {
+        let c = ATASCII_NORMAL.decode(b);
+        assert!(encoder.encode(c, false, &mut atascii));
+        assert!(encoder.encode(c, true, &mut atascii));
+    }
+    assert!(encoder.encode('\n', true, &mut atascii));
+    assert_eq!(encoder.state(), CodepageState { mode: 0, reverse: false });
+
+    let expected: Vec<u8> = (0..0x80).flat_map(|b| vec![b, if b == 0x1B { 0x1B } else { b | 0x80 }]).chain(Some(0x9B)).collect();
+    assert_eq!(atascii, expected);
+
+    assert!(!encoder.encode('£', false, &mut atascii));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn conversion() {
+    let all: Vec<u8> = (0..=0xFF).collect();
+
+    let mut unicode = String::new();
+    ATASCII.decode_into(&all, &mut unicode);
+    assert_eq!(unicode.chars().count(), 0x100);
+
+    let mut streamed = String::new();
+    Cp437Reader::with_capacity(7, &all[..], &ATASCII).read_to_string(&mut streamed).unwrap();
+    assert_eq!(streamed, unicode);
+
+    let mut atascii = vec![];
+    ATASCII.encode_into(&unicode, &mut atascii).unwrap();
+    let mut round_trip = String::new();
+    ATASCII.decode_into(&atascii, &mut round_trip);
+    assert_eq!(round_trip, unicode);
+}
@@ -0,0 +1,67 @@
+/// Test a mode of a stateful codepage against `test-data/$data/all.utf8`,
+/// the characters repeated in `0xC0..=0xFF` encoding to their positions there.
+macro_rules! mode_tests {
+    ($name:ident, $dialect:ident, $data:literal, [$($undefined:expr),*], $repeated:expr) => {
+        mod $name {
+            use codepage_437::$dialect;
+            #[cfg(feature = "alloc")]
+            use codepage_437::{FromCp437, ToCp437};
+
+
+            const ALL_UTF8: &str = include_str!(concat!("../../test-data/", $data, "/all.utf8"));
+            const UNDEFINED: &[u8] = &[$($undefined),*];
+
+
+            #[test]
+            fn decode() {
+                assert_eq!(ALL_UTF8.chars().count(), 0x100);
+                for (b, c) in ALL_UTF8.chars().enumerate().map(|(b, c)| (b as u8, c)) {
+                    assert_eq!($dialect.decode(b), c);
+                    assert_eq!($dialect.defined(b), !UNDEFINED.contains(&b));
+
+                    if $dialect.overlap_cp437(b) {
+                        assert_eq!(b as char, c);
+                    }
+                }
+            }
+
+            #[test]
+            fn encode() {
+                for b in (0..=0xFF).filter(|&b| $dialect.defined(b)) {
+                    let code = $dialect.encode($dialect.decode(b)).unwrap();
+                    assert_eq!($dialect.decode(code), $dialect.decode(b));
+                    assert!(code == b || ($repeated(b) && code >= 0xC0), "0x{:02X} encodes to 0x{:02X}", b, code);
+                }
+                for &b in UNDEFINED {
+                    assert_eq!($dialect.try_decode(b), None);
+                }
+            }
+
+            #[test]
+            #[cfg(feature = "alloc")]
+            fn conversion() {
+                let (cp437, unicode): (Vec<u8>, String) =
+                    ALL_UTF8.chars().enumerate().map(|(b, c)| (b as u8, c)).filter(|&(b, c)| $dialect.encode(c) == Some(b)).unzip();
+
+                assert_eq!(String::from_cp437(cp437.clone(), &$dialect), unicode);
+                assert_eq!(unicode.to_cp437(&$dialect).unwrap(), &cp437[..]);
+            }
+        }
+    }
+}
+
+mode_tests!(petscii_unshifted, PETSCII_UNSHIFTED, "petscii_unshifted", [0x0E, 0x12, 0x8E, 0x92], |b| (0x60..=0x7F).contains(&b) || b >= 0xA0);
+mode_tests!(petscii_shifted, PETSCII_SHIFTED, "petscii_shifted", [0x0E, 0x12, 0x8E, 0x92], |b| (0x60..=0x7F).contains(&b) || b >= 0xA0);
+mode_tests!(atascii_normal, ATASCII_NORMAL, "atascii_normal",
+            [0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F,
+             0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A,       0x9C, 0x9D, 0x9E, 0x9F,
+             0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xAB, 0xAC, 0xAD, 0xAE, 0xAF,
+             0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xBB, 0xBC, 0xBD, 0xBE, 0xBF,
+             0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF,
+             0xD0, 0xD1, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xDB, 0xDC, 0xDD, 0xDE, 0xDF,
+             0xE0, 0xE1, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xEB, 0xEC, 0xED, 0xEE, 0xEF,
+             0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF],
+            |_| false);
+
+mod petscii;
+mod atascii;
@@ -0,0 +1,118 @@
+use codepage_437::{PETSCII, CodepageState};
+#[cfg(feature = "alloc")]
+use codepage_437::{PETSCII_UNSHIFTED, PETSCII_SHIFTED, CodepageError};
+#[cfg(feature = "std")]
+use codepage_437::{Cp437Reader, Cp437Writer};
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+
+// "Ready." in shifted mode, then a ♠, a reverse ♥, and a ♥ in unshifted mode
+#[cfg(feature = "alloc")]
+const READY: &[u8] = &[0x0E, 0xD2, 0x45, 0x41, 0x44, 0x59, 0x2E, 0x0D, 0x8E, 0xC1, 0x12, 0xD3, 0x92, 0xD3];
+
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode() {
+    let mut unicode = String::new();
+    PETSCII.decode_into(READY, &mut unicode);
+    assert_eq!(unicode, "Ready.\r♠♥♥");
+
+    let mut decoder = PETSCII.decoder();
+    let decoded: Vec<_> = READY.iter().filter_map(|&b| decoder.decode(b).map(|c| (c, decoder.state()))).collect();
+    assert_eq!(decoded,
+               [('R', CodepageState { mode: 1, reverse: false }),
+                ('e', CodepageState { mode: 1, reverse: false }),
+                ('a', CodepageState { mode: 1, reverse: false }),
+                ('d', CodepageState { mode: 1, reverse: false }),
+                ('y', CodepageState { mode: 1, reverse: false }),
+                ('.', CodepageState { mode: 1, reverse: false }),
+                ('\r', CodepageState { mode: 1, reverse: false }),
+                ('♠', CodepageState { mode: 0, reverse: false }),
+                ('♥', CodepageState { mode: 0, reverse: true }),
+                ('♥', CodepageState { mode: 0, reverse: false })]);
+}
+
+#[test]
+fn switches() {
+    let mut decoder = PETSCII.decoder();
+    for &b in &[0x0E, 0x8E, 0x12, 0x92] {
+        assert_eq!(decoder.decode(b), None);
+    }
+
+    decoder.decode(0x12);
+    assert_eq!(decoder.decode(0x0D), Some('\r'));
+    assert!(!decoder.state().reverse);
+    decoder.decode(0x12);
+    assert_eq!(decoder.decode(0x8D), Some('\u{8D}'));
+    assert!(!decoder.state().reverse);
+
+    decoder.set_state(CodepageState { mode: 1, reverse: true });
+    assert_eq!(decoder.decode(0x41), Some('a'));
+    assert!(decoder.state().reverse);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode() {
+    let mut petscii = vec![];
+    PETSCII.encode_into("Ready.\r♠♥", &mut petscii).unwrap();
+    assert_eq!(petscii, &[0x52, 0x0E, 0x45, 0x41, 0x44, 0x59, 0x2E, 0x0D, 0x8E, 0xC1, 0xD3]);
+
+    let mut encoder = PETSCII.encoder();
+    let mut petscii = vec![];
+    for &(c, reverse) in &[('♥', true), ('♥', true), ('\r', true), ('♥', false), ('✓', false)] {
+        assert!(encoder.encode(c, reverse, &mut petscii));
+    }
+    assert_eq!(petscii, &[0x12, 0xD3, 0xD3, 0x0D, 0xD3, 0x0E, 0xFA]);
+    assert_eq!(encoder.state(), CodepageState { mode: 1, reverse: false });
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn round_trip() {
+    let all: String = (0..=0xFF).flat_map(|b| vec![PETSCII_UNSHIFTED.decode(b), PETSCII_SHIFTED.decode(b)]).collect();
+    let all: String = all.chars().filter(|&c| !"\u{E}\u{12}\u{8E}\u{92}".contains(c)).collect();
+
+    let mut petscii = vec![];
+    PETSCII.encode_into(&all, &mut petscii).unwrap();
+
+    let mut unicode = String::new();
+    PETSCII.decode_into(&petscii, &mut unicode);
+    assert_eq!(unicode, all);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn unrepresentable() {
+    let mut petscii = vec![];
+    assert_eq!(PETSCII.encode_into("Ready ☺", &mut petscii),
+               Err(CodepageError {
+                   representable_up_to: 6,
+                   char_index: 6,
+                   unrepresentable: '☺',
+               }));
+    assert_eq!(petscii, &[0x52, 0x0E, 0x45, 0x41, 0x44, 0x59, 0x20]);
+
+    // Switches aren't characters
+    assert!(!PETSCII.encoder().encode('\u{E}', false, &mut petscii));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream() {
+    let mut unicode = String::new();
+    Cp437Reader::with_capacity(1, READY, &PETSCII).read_to_string(&mut unicode).unwrap();
+    assert_eq!(unicode, "Ready.\r♠♥♥");
+
+    let mut petscii = vec![];
+    {
+        let mut writer = Cp437Writer::new(&mut petscii, &PETSCII);
+        writer.write_all("Re".as_bytes()).unwrap();
+        writer.write_all("ady.\r♠".as_bytes()).unwrap();
+        writer.write_all("♥".as_bytes()).unwrap();
+        writer.flush().unwrap();
+    }
+    assert_eq!(petscii, &[0x52, 0x0E, 0x45, 0x41, 0x44, 0x59, 0x2E, 0x0D, 0x8E, 0xC1, 0xD3]);
+}
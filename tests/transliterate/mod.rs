@@ -0,0 +1,70 @@
+use codepage_437::transliteration;
+#[cfg(feature = "alloc")]
+use codepage_437::{CP437_WINGDINGS, CP437_CONTROL, CP850_CONTROL, CP1258, Transliterate, Cp437Error};
+#[cfg(feature = "alloc")]
+use std::borrow::Cow;
+
+
+#[test]
+fn table() {
+    assert_eq!(transliteration('Ź'), Some("Z"));
+    assert_eq!(transliteration('ǻ'), Some("a"));
+    assert_eq!(transliteration('ά'), Some("α"));
+    assert_eq!(transliteration('—'), Some("-"));
+    assert_eq!(transliteration('‘'), Some("'"));
+    assert_eq!(transliteration('€'), Some("E"));
+    assert_eq!(transliteration('Ł'), Some("L"));
+    assert_eq!(transliteration('✔'), Some("✓"));
+
+    assert_eq!(transliteration('a'), None);
+    assert_eq!(transliteration('ジ'), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn representable_unchanged() {
+    assert_eq!("Local news".transliterate_to_cp437(&CP437_CONTROL), Ok(Cow::Borrowed(&b"Local news"[..])));
+    assert_eq!("₧½ Melanesiæ".transliterate_to_cp437(&CP437_CONTROL), Ok(b"\x9E\xAB Melanesi\x91"[..].into()));
+    assert_eq!("€".transliterate_to_cp437(&CP437_CONTROL), Ok(b"\xEE"[..].into()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn transliterated() {
+    assert_eq!("Łódź – “ǻ” … ά".transliterate_to_cp437(&CP437_CONTROL), Ok(b"L\xA2dz - \"a\" ... \xE0"[..].into()));
+    assert_eq!("™ ✔ ▪".transliterate_to_cp437(&CP437_CONTROL), Ok(b"TM \xFB \xFE"[..].into()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decomposed() {
+    assert_eq!("Cafe\u{301}".transliterate_to_cp437(&CP437_CONTROL), Ok(b"Cafe"[..].into()));
+    assert_eq!("Z\u{307}o\u{301}\u{328}ł".transliterate_to_cp437(&CP437_CONTROL), Ok(b"Zol"[..].into()));
+    // Unlike when precomposed, as the base is encoded before the mark is seen
+    assert_eq!("Żół".transliterate_to_cp437(&CP437_CONTROL), Ok(b"Z\xA2l"[..].into()));
+    // Representable marks are kept
+    assert_eq!("Cafe\u{301}".transliterate_to_cp437(&CP1258), Ok(b"Cafe\xEC"[..].into()));
+
+    assert_eq!("\u{301}e".transliterate_to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 0, char_index: 0, unrepresentable: '\u{301}' }));
+    assert_eq!("e\u{20DD}".transliterate_to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 1, char_index: 1, unrepresentable: '\u{20DD}' }));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dialect_dependent() {
+    assert_eq!("•".transliterate_to_cp437(&CP437_CONTROL), Ok(b"\xF9"[..].into()));
+    assert_eq!("•".transliterate_to_cp437(&CP437_WINGDINGS), Ok(b"\x07"[..].into()));
+
+    assert_eq!("⇒".transliterate_to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 0, char_index: 0, unrepresentable: '⇒' }));
+    assert_eq!("⇒".transliterate_to_cp437(&CP437_WINGDINGS), Ok(b"\x1A"[..].into()));
+
+    assert_eq!("Łódź".transliterate_to_codepage(&CP850_CONTROL), Ok(b"L\xA2dz"[..].into()));
+    assert_eq!("Łódź".transliterate_to_codepage(&CP437_CONTROL), "Łódź".transliterate_to_cp437(&CP437_CONTROL));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn untransliterable() {
+    assert_eq!("Żurek ジ".transliterate_to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 7, char_index: 6, unrepresentable: 'ジ' }));
+    assert_eq!("Żurek ジ".to_string().transliterate_to_cp437(&CP437_CONTROL), Err(Cp437Error { representable_up_to: 7, char_index: 6, unrepresentable: 'ジ' }));
+}
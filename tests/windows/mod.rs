@@ -0,0 +1,10 @@
+single_byte_tests!(cp1250, CP1250, "cp1250", [0x81, 0x83, 0x88, 0x90, 0x98]);
+single_byte_tests!(cp1251, CP1251, "cp1251", [0x98]);
+single_byte_tests!(cp1252, CP1252, "cp1252", [0x81, 0x8D, 0x8F, 0x90, 0x9D]);
+single_byte_tests!(cp1253, CP1253, "cp1253", [0x81, 0x88, 0x8A, 0x8C, 0x8D, 0x8E, 0x8F, 0x90, 0x98, 0x9A, 0x9C, 0x9D, 0x9E, 0x9F, 0xAA, 0xD2, 0xFF]);
+single_byte_tests!(cp1254, CP1254, "cp1254", [0x81, 0x8D, 0x8E, 0x8F, 0x90, 0x9D, 0x9E]);
+single_byte_tests!(cp1255, CP1255, "cp1255", [0x81, 0x8A, 0x8C, 0x8D, 0x8E, 0x8F, 0x90, 0x9A, 0x9C, 0x9D, 0x9E, 0x9F, 0xCA, 0xD9, 0xDA, 0xDB,
+                                              0xDC, 0xDD, 0xDE, 0xDF, 0xFB, 0xFC, 0xFF]);
+single_byte_tests!(cp1256, CP1256, "cp1256", []);
+single_byte_tests!(cp1257, CP1257, "cp1257", [0x81, 0x83, 0x88, 0x8A, 0x8C, 0x90, 0x98, 0x9A, 0x9C, 0x9F, 0xA1, 0xA5]);
+single_byte_tests!(cp1258, CP1258, "cp1258", [0x81, 0x8A, 0x8D, 0x8E, 0x8F, 0x90, 0x9A, 0x9D, 0x9E]);